@@ -0,0 +1,261 @@
+//! Execução não interativa da Máquina de Turing. Cada cadeia de entrada é processada
+//! até a máquina aceitá-la ou rejeitá-la, sem o uso da interface de terminal.
+
+use std::io::Write;
+
+use crate::{
+    machine::{
+        multi::{MultiMachine, MultiSeptuple},
+        nondet::{NondetMachine, NondetSeptuple},
+        sep::Septuple,
+        symbol::{self, split_input, Symbol},
+        trace, Acceptance, Machine, OutputMode, DEFAULT_STEP_LIMIT,
+    },
+    Result,
+};
+
+/// Resultado do processamento de uma única cadeia.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum Outcome {
+    /// O processamento terminou, com o resultado e a fita final. Caso a máquina compute
+    /// uma função e tenha parado, `output` contém o resultado extraído da fita.
+    Halted {
+        acceptance: Acceptance,
+        tape: Vec<Symbol>,
+        output: Option<Vec<Symbol>>,
+    },
+    /// A cadeia possui símbolos que não são símbolos de entrada.
+    InvalidInput,
+}
+
+/// Contagem dos resultados de uma execução em lote.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct Summary {
+    pub accepted: usize,
+    pub rejected: usize,
+    /// Cadeias em que a máquina parou em um estado de parada, sem aceitar ou rejeitar.
+    pub halted: usize,
+    /// Cadeias em que a máquina não parou (limite de passos ou laço).
+    pub undecided: usize,
+    pub errors: usize,
+}
+
+impl Summary {
+    /// Contabiliza `acceptance` e retorna sua descrição na saída.
+    fn count(&mut self, acceptance: Acceptance) -> String {
+        match acceptance {
+            Acceptance::Accepted => {
+                self.accepted += 1;
+                "ACEITA".to_string()
+            }
+            Acceptance::Rejected(rejection) => {
+                self.rejected += 1;
+                format!("REJEITA ({rejection})")
+            }
+            Acceptance::Halted => {
+                self.halted += 1;
+                "PARA".to_string()
+            }
+            Acceptance::Exhausted => {
+                self.undecided += 1;
+                "LIMITE".to_string()
+            }
+            Acceptance::Looping => {
+                self.undecided += 1;
+                "LAÇO".to_string()
+            }
+        }
+    }
+}
+
+/// Opções aplicadas à máquina de cada cadeia processada.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Options {
+    /// Número máximo de transições por cadeia. Caso [`None`], não há limite.
+    pub step_limit: Option<usize>,
+    /// Caso `true`, a repetição de uma configuração encerra o processamento.
+    pub detect_loops: bool,
+    /// Caso [`Some`], a máquina computa uma função e o resultado é extraído da fita
+    /// final de acordo com o modo.
+    pub output: Option<OutputMode>,
+    /// Caso [`Some`], o registro passo a passo de cada cadeia é escrito antes de seu
+    /// resultado.
+    pub trace: Option<trace::Format>,
+}
+
+impl Default for Options {
+    fn default() -> Self {
+        Options {
+            step_limit: Some(DEFAULT_STEP_LIMIT),
+            detect_loops: true,
+            output: None,
+            trace: None,
+        }
+    }
+}
+
+/// Processa `input` na máquina definida por `sep` até que ela pare ou atinja algum dos
+/// limites definidos em `options`. Os símbolos de `input` são separados como em
+/// [`split_input`]. O registro do processamento, caso requisitado, é escrito em `out`.
+pub fn run_input<W: Write>(
+    sep: &Septuple,
+    input: &str,
+    options: Options,
+    out: &mut W,
+) -> Result<Outcome> {
+    let mut machine = match Machine::new(sep, split_input(input, &sep.input_symbols)) {
+        Ok(machine) => machine,
+        Err(_) => return Ok(Outcome::InvalidInput),
+    };
+    machine.set_step_limit(options.step_limit);
+    machine.set_loop_detection(options.detect_loops);
+
+    let acceptance = match options.trace {
+        Some(format) => {
+            trace::write(format, machine.trace(), out)?;
+            // o registro só termina quando a máquina para ou atinge um limite
+            machine.acceptance().unwrap()
+        }
+        None => machine.run(),
+    };
+    let output = match options.output {
+        Some(mode) if acceptance.halted() => Some(machine.output(mode)),
+        _ => None,
+    };
+    Ok(Outcome::Halted {
+        acceptance,
+        tape: machine.tape().to_vec(),
+        output,
+    })
+}
+
+/// Processa cada uma das cadeias de `inputs`, escrevendo em `out` uma linha por cadeia
+/// com o resultado (ACEITA, REJEITA, PARA, LIMITE, LAÇO ou ERRO) e a fita final ou, caso a
+/// máquina compute uma função, o resultado da função.
+pub fn run<W, I, S>(sep: &Septuple, inputs: I, options: Options, out: &mut W) -> Result<Summary>
+where
+    W: Write,
+    I: IntoIterator<Item = S>,
+    S: AsRef<str>,
+{
+    let mut summary = Summary::default();
+
+    for input in inputs {
+        let input = input.as_ref();
+        match run_input(sep, input, options, out)? {
+            Outcome::Halted {
+                acceptance,
+                tape,
+                output,
+            } => {
+                let verdict = summary.count(acceptance);
+                match output {
+                    Some(output) => {
+                        let output = symbol::join(&output);
+                        writeln!(out, "{input:?}: {verdict}, resultado: {output}")?;
+                    }
+                    None => {
+                        let tape = symbol::join(&tape);
+                        writeln!(out, "{input:?}: {verdict}, fita: {tape}")?;
+                    }
+                }
+            }
+            Outcome::InvalidInput => {
+                summary.errors += 1;
+                writeln!(out, "{input:?}: ERRO, a cadeia possui símbolos inválidos")?;
+            }
+        }
+    }
+
+    Ok(summary)
+}
+
+/// Processa cada uma das cadeias de `inputs` em uma máquina não determinística,
+/// escrevendo em `out` uma linha por cadeia com o resultado e, caso a cadeia seja
+/// aceita, a fita final do ramo que a aceitou. Caso requisitado, o registro escrito é o
+/// caminho do ramo que aceitou a cadeia.
+pub fn run_nondet<W, I, S>(
+    sep: &NondetSeptuple,
+    inputs: I,
+    options: Options,
+    out: &mut W,
+) -> Result<Summary>
+where
+    W: Write,
+    I: IntoIterator<Item = S>,
+    S: AsRef<str>,
+{
+    let mut summary = Summary::default();
+
+    for input in inputs {
+        let input = input.as_ref();
+        let mut machine =
+            match NondetMachine::new(sep, split_input(input, &sep.septuple.input_symbols)) {
+                Ok(machine) => machine,
+                Err(_) => {
+                    summary.errors += 1;
+                    writeln!(out, "{input:?}: ERRO, a cadeia possui símbolos inválidos")?;
+                    continue;
+                }
+            };
+        machine.set_limit(options.step_limit);
+
+        let run = machine.run();
+        let verdict = summary.count(run.acceptance);
+        match run.path {
+            Some(path) => {
+                // o caminho sempre possui ao menos a configuração inicial
+                let tape = symbol::join(&path.last().unwrap().tape);
+                if let Some(format) = options.trace {
+                    trace::write(format, path, out)?;
+                }
+                writeln!(out, "{input:?}: {verdict}, fita: {tape}")?;
+            }
+            None => {
+                let explored = run.explored;
+                writeln!(
+                    out,
+                    "{input:?}: {verdict}, configurações exploradas: {explored}"
+                )?;
+            }
+        }
+    }
+
+    Ok(summary)
+}
+
+/// Processa cada uma das cadeias de `inputs` em uma máquina multifita, escrevendo em
+/// `out` uma linha por cadeia com o resultado e o conteúdo final de cada fita.
+pub fn run_multi<W, I, S>(
+    sep: &MultiSeptuple,
+    inputs: I,
+    options: Options,
+    out: &mut W,
+) -> Result<Summary>
+where
+    W: Write,
+    I: IntoIterator<Item = S>,
+    S: AsRef<str>,
+{
+    let mut summary = Summary::default();
+
+    for input in inputs {
+        let input = input.as_ref();
+        let mut machine =
+            match MultiMachine::new(sep, split_input(input, &sep.septuple.input_symbols)) {
+                Ok(machine) => machine,
+                Err(_) => {
+                    summary.errors += 1;
+                    writeln!(out, "{input:?}: ERRO, a cadeia possui símbolos inválidos")?;
+                    continue;
+                }
+            };
+        machine.set_step_limit(options.step_limit);
+
+        let verdict = summary.count(machine.run());
+        let tapes: Vec<String> = machine.tapes().into_iter().map(symbol::join).collect();
+        writeln!(out, "{input:?}: {verdict}, fitas: {}", tapes.join(" | "))?;
+    }
+
+    Ok(summary)
+}
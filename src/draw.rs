@@ -1,25 +1,36 @@
 mod aux;
+mod tape;
 
-use std::collections::HashSet;
+use indexmap::IndexSet;
 
 use tui::{
     backend::Backend,
-    layout::{Alignment, Constraint, Layout},
+    layout::{Alignment, Constraint, Direction, Layout},
     style::{Color, Modifier, Style},
     text::{Span, Spans},
     widgets::{Block, BorderType, Borders, Paragraph, Wrap},
     Frame,
 };
 
-use crate::machine::{Acceptance, Machine};
+use crate::{
+    machine::{
+        breakpoint::{Breakpoint, InvalidBreakpointError},
+        multi::MultiMachine,
+        symbol::{self, Symbol},
+        Acceptance, Machine,
+    },
+    playback::Playback,
+};
 
 pub use aux::original_tape_spans;
 
+use self::tape::TapeView;
+
 /// Desenha a tela para o input da fita.
 pub fn tape_input<B: Backend>(
     frame: &mut Frame<B>,
     tape_buffer: &str,
-    input_symbols: &HashSet<char>,
+    input_symbols: &IndexSet<Symbol>,
 ) {
     let tape_outer_block = Block::default()
         .title("Digite a fita")
@@ -52,15 +63,21 @@ pub fn tape_input<B: Backend>(
     ];
 
     frame.render_widget(tape_paragraph, chunks[0]);
-    let alphabet_paragraph =
-        Paragraph::new(format!("Símbolos de entrada: {input_symbols:?}")).wrap(Wrap { trim: true });
+    let mut alphabet = format!("Símbolos de entrada: {input_symbols:?}");
+    if !input_symbols.iter().all(Symbol::is_char) {
+        alphabet += &format!(
+            "\nSepare os símbolos com espaços ou \"{}\"",
+            symbol::DELIMITER
+        );
+    }
+    let alphabet_paragraph = Paragraph::new(alphabet).wrap(Wrap { trim: true });
     frame.render_widget(alphabet_paragraph, chunks[1]);
     let help_paragraph = Paragraph::new(Spans(help_spans)).block(aux::help_block());
     frame.render_widget(help_paragraph, chunks[2]);
 }
 
 /// Desenha a tela de erro de input da fita.
-pub fn bad_tape<B: Backend>(frame: &mut Frame<B>, input_symbols: &HashSet<char>) {
+pub fn bad_tape<B: Backend>(frame: &mut Frame<B>, input_symbols: &IndexSet<Symbol>) {
     let message_outer_block = Block::default()
         .title("Digite a fita")
         .title_alignment(Alignment::Left)
@@ -93,12 +110,28 @@ pub fn bad_tape<B: Backend>(frame: &mut Frame<B>, input_symbols: &HashSet<char>)
     frame.render_widget(help_paragraph, chunks[2]);
 }
 
+/// Informações da tela de processamento exibidas junto da máquina.
+pub struct MachineView<'v> {
+    pub acceptance: Option<Acceptance>,
+    /// Caso [`Some`], é exibido como o resultado da função computada pela máquina.
+    pub output: Option<&'v [Symbol]>,
+    pub playback: &'v Playback,
+    /// O ponto de parada atingido pela configuração atual, exibido no título da fita.
+    pub hit: Option<&'v Breakpoint>,
+    /// Deslocamento, em células, da janela da fita em relação à cabeça.
+    pub pan: isize,
+    /// Maior passo já alcançado, exibido como o fim da linha do tempo.
+    pub furthest: usize,
+    /// Passo selecionado no histórico. Caso [`None`], o passo atual é selecionado.
+    pub history_cursor: Option<usize>,
+}
+
 /// Desenha a tela de processamento da máquina atual.
 pub fn machine<B: Backend>(
     frame: &mut Frame<B>,
     machine: &Machine,
-    acceptance: Option<Acceptance>,
     original_tape: Spans,
+    view: &MachineView,
 ) {
     let outer_block = Block::default()
         .title("Máquina de Turing")
@@ -113,42 +146,245 @@ pub fn machine<B: Backend>(
         .title_alignment(Alignment::Left);
 
     let screen_chunks = aux::four_split(frame.size());
-    let active_tape = aux::active_tape_spans(machine.tape(), machine.current_position());
     let mut tape_title = vec![Span::from(format!("Fita @ {}", machine.current_state()))];
-    if let Some(accept) = acceptance {
-        match accept {
-            Acceptance::Accepted => tape_title.push(Span::styled(
-                " aceitada",
-                Style::default()
-                    .fg(Color::LightBlue)
-                    .add_modifier(Modifier::BOLD),
-            )),
-            Acceptance::Rejected => tape_title.push(Span::styled(
-                " rejeitada",
-                Style::default().fg(Color::Red).add_modifier(Modifier::BOLD),
-            )),
-        }
+    if let Some(acceptance) = view.acceptance {
+        tape_title.push(aux::acceptance_span(acceptance));
     }
-    let help_spans = vec![
-        Span::styled("<Esc> ", Style::default().fg(Color::Rgb(255, 140, 0))),
-        Span::from("voltar "),
-        Span::styled("< ⟵ > ", Style::default().fg(Color::Rgb(255, 140, 0))),
-        Span::from("desfazer transição "),
-        Span::styled("< ⟶ > ", Style::default().fg(Color::Rgb(255, 140, 0))),
-        Span::from("aplicar transição "),
-    ];
-    let help_paragraph = Paragraph::new(Spans(help_spans)).block(aux::help_block());
+    if let Some(hit) = view.hit {
+        tape_title.push(Span::styled(
+            format!(" ponto de parada: {}", hit.describe()),
+            Style::default()
+                .fg(Color::Magenta)
+                .add_modifier(Modifier::BOLD),
+        ));
+    }
+    if let Some(output) = view.output {
+        let output = symbol::join(output);
+        tape_title.push(Span::styled(
+            format!(" resultado: {output}"),
+            Style::default()
+                .fg(Color::LightGreen)
+                .add_modifier(Modifier::BOLD),
+        ));
+    }
+    let help_paragraph = aux::machine_help_paragraph(view.playback, true);
 
     let tape_title = Spans::from(tape_title);
     let tape_block = original_tape_block.clone().title(tape_title);
 
-    let active_tape = Paragraph::new(active_tape).block(tape_block);
+    let active_tape = TapeView::new(machine.tape(), machine.head_index(), machine.origin())
+        .pan(view.pan)
+        .block(tape_block);
     frame.render_widget(active_tape, screen_chunks[0]);
     let original_tape = Paragraph::new(original_tape).block(original_tape_block);
     frame.render_widget(original_tape, screen_chunks[1]);
+    let middle_chunks = Layout::default()
+        .constraints([Constraint::Min(0), Constraint::Length(1)])
+        .split(screen_chunks[2]);
+    let septuple_chunks = Layout::default()
+        .direction(Direction::Horizontal)
+        .constraints([
+            Constraint::Percentage(30),
+            Constraint::Percentage(35),
+            Constraint::Percentage(35),
+        ])
+        .split(middle_chunks[0]);
     frame.render_widget(
         aux::septuple_paragraph(machine.septuple()),
-        screen_chunks[2],
+        septuple_chunks[0],
+    );
+    // a transição só está prestes a ser aplicada enquanto a máquina processa a fita
+    let active = view
+        .acceptance
+        .is_none()
+        .then(|| {
+            machine.septuple().find_transition(
+                machine.current_state(),
+                &machine.tape()[machine.head_index()],
+            )
+        })
+        .flatten();
+    let previous = machine.last_applied().map(|applied| applied.transition);
+    let (table, mut table_state) = aux::transition_table(machine.septuple(), active, previous);
+    frame.render_stateful_widget(table, septuple_chunks[1], &mut table_state);
+    let selected = view
+        .history_cursor
+        .unwrap_or(usize::MAX)
+        .min(machine.steps());
+    let (table, mut table_state) =
+        aux::history_table(machine, selected, septuple_chunks[2].height as usize);
+    frame.render_stateful_widget(table, septuple_chunks[2], &mut table_state);
+    frame.render_widget(
+        aux::timeline_gauge(machine.steps(), view.furthest),
+        middle_chunks[1],
     );
     frame.render_widget(help_paragraph, screen_chunks[3]);
 }
+
+/// Desenha a tela de leitura do passo para o qual a máquina deve ir. Caso `invalid`
+/// seja `true`, o último valor digitado não era um número de passo válido.
+pub fn step_input<B: Backend>(frame: &mut Frame<B>, buffer: &str, invalid: bool) {
+    let (title, color) = if invalid {
+        ("Passo inválido, digite um número não negativo", Color::Red)
+    } else {
+        ("Ir para o passo", Color::Green)
+    };
+    let input_block = Block::default()
+        .title(title)
+        .title_alignment(Alignment::Left)
+        .borders(Borders::ALL)
+        .border_style(Style::default().fg(color));
+
+    let chunks = Layout::default()
+        .constraints([
+            Constraint::Length(3),
+            Constraint::Min(0),
+            Constraint::Length(3),
+        ])
+        .split(frame.size());
+
+    let bold = Style::default().add_modifier(Modifier::BOLD);
+    let gray_background = Style::default().bg(Color::Gray);
+    let input_spans = Spans(vec![
+        Span::styled(buffer, bold),
+        Span::styled(" ", gray_background), // "cursor"
+    ]);
+    frame.render_widget(Paragraph::new(input_spans).block(input_block), chunks[0]);
+
+    let help_spans = vec![
+        Span::styled("<Esc> ", Style::default().fg(Color::Rgb(255, 140, 0))),
+        Span::from("voltar "),
+        Span::styled("<Enter> ", Style::default().fg(Color::Rgb(255, 140, 0))),
+        Span::from("ir para o passo"),
+    ];
+    let help_paragraph = Paragraph::new(Spans(help_spans)).block(aux::help_block());
+    frame.render_widget(help_paragraph, chunks[2]);
+}
+
+/// Desenha a tela de edição dos pontos de parada. O ponto de índice `selected` é
+/// destacado e, caso `error` seja [`Some`], o erro é exibido no título do input.
+pub fn breakpoints<B: Backend>(
+    frame: &mut Frame<B>,
+    buffer: &str,
+    breakpoints: &[Breakpoint],
+    selected: usize,
+    error: Option<&InvalidBreakpointError>,
+) {
+    let (title, color) = match error {
+        Some(error) => (error.to_string(), Color::Red),
+        None => ("Novo ponto de parada".to_string(), Color::Green),
+    };
+    let input_block = Block::default()
+        .title(title)
+        .title_alignment(Alignment::Left)
+        .borders(Borders::ALL)
+        .border_style(Style::default().fg(color));
+
+    let chunks = Layout::default()
+        .constraints([
+            Constraint::Length(3),
+            Constraint::Min(0),
+            Constraint::Length(3),
+            Constraint::Length(3),
+        ])
+        .split(frame.size());
+
+    let bold = Style::default().add_modifier(Modifier::BOLD);
+    let gray_background = Style::default().bg(Color::Gray);
+    let input_spans = Spans(vec![
+        Span::styled(buffer, bold),
+        Span::styled(" ", gray_background), // "cursor"
+    ]);
+    frame.render_widget(Paragraph::new(input_spans).block(input_block), chunks[0]);
+
+    let list: Vec<_> = breakpoints
+        .iter()
+        .enumerate()
+        .map(|(i, breakpoint)| {
+            let style = if i == selected {
+                Style::default().add_modifier(Modifier::REVERSED)
+            } else {
+                Style::default()
+            };
+            Spans::from(Span::styled(
+                format!("{breakpoint:<16} {}", breakpoint.describe()),
+                style,
+            ))
+        })
+        .collect();
+    let list_block = Block::default()
+        .title("Pontos de parada")
+        .borders(Borders::ALL)
+        .border_type(BorderType::Double);
+    frame.render_widget(Paragraph::new(list).block(list_block), chunks[1]);
+
+    let syntax = "<estado>, <estado> <símbolo>, #<passo> ou @<posição>";
+    frame.render_widget(
+        Paragraph::new(format!("Formatos: {syntax}")).wrap(Wrap { trim: true }),
+        chunks[2],
+    );
+
+    let help_spans = vec![
+        Span::styled("<Esc> ", Style::default().fg(Color::Rgb(255, 140, 0))),
+        Span::from("voltar "),
+        Span::styled("<Enter> ", Style::default().fg(Color::Rgb(255, 140, 0))),
+        Span::from("adicionar "),
+        Span::styled("< ↑/↓ > ", Style::default().fg(Color::Rgb(255, 140, 0))),
+        Span::from("selecionar "),
+        Span::styled("<Delete> ", Style::default().fg(Color::Rgb(255, 140, 0))),
+        Span::from("remover "),
+    ];
+    let help_paragraph = Paragraph::new(Spans(help_spans)).block(aux::help_block());
+    frame.render_widget(help_paragraph, chunks[3]);
+}
+
+/// Desenha a tela de processamento de uma máquina multifita, com as fitas empilhadas.
+pub fn multi_machine<B: Backend>(
+    frame: &mut Frame<B>,
+    machine: &MultiMachine,
+    acceptance: Option<Acceptance>,
+    playback: &Playback,
+    pan: isize,
+) {
+    let outer_block = Block::default()
+        .title("Máquina de Turing multifita")
+        .title_alignment(Alignment::Center)
+        .borders(Borders::ALL);
+    frame.render_widget(outer_block, frame.size());
+
+    let tapes = machine.tapes();
+    let mut constraints = vec![Constraint::Length(4); tapes.len()];
+    constraints.extend([Constraint::Min(0), Constraint::Length(4)]);
+    let screen_chunks = Layout::default()
+        .direction(Direction::Vertical)
+        .margin(1)
+        .constraints(constraints)
+        .split(frame.size());
+
+    let heads = machine.head_indices().into_iter().zip(machine.origins());
+    for (i, (tape, (head, origin))) in tapes.iter().zip(heads).enumerate() {
+        let mut tape_title = vec![Span::from(format!("Fita {}", i + 1))];
+        if i == 0 {
+            tape_title.push(Span::from(format!(" @ {}", machine.current_state())));
+            if let Some(acceptance) = acceptance {
+                tape_title.push(aux::acceptance_span(acceptance));
+            }
+        }
+        let tape_block = Block::default()
+            .borders(Borders::ALL)
+            .border_type(BorderType::Double)
+            .title(Spans::from(tape_title))
+            .title_alignment(Alignment::Left);
+        let active_tape = TapeView::new(tape, head, origin).pan(pan).block(tape_block);
+        frame.render_widget(active_tape, screen_chunks[i]);
+    }
+
+    frame.render_widget(
+        aux::multi_septuple_paragraph(machine.septuple()),
+        screen_chunks[tapes.len()],
+    );
+    frame.render_widget(
+        aux::machine_help_paragraph(playback, false),
+        screen_chunks[tapes.len() + 1],
+    );
+}
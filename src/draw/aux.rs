@@ -1,14 +1,24 @@
 //! Esse submódulo de [`draw`](super) possui funções auxiliares, destinadas à construção
 //! de elementos a serem desenhados.
 
+use std::ptr;
+
 use tui::{
     layout::{Constraint, Direction, Layout, Rect},
-    style::{Color, Style},
+    style::{Color, Modifier, Style},
     text::{Span, Spans},
-    widgets::{Block, BorderType, Borders, Paragraph, Wrap},
+    widgets::{Block, BorderType, Borders, Gauge, Paragraph, Row, Table, TableState, Wrap},
 };
 
-use crate::machine::sep::{Movement, Septuple};
+use crate::{
+    machine::{
+        multi::MultiSeptuple,
+        sep::{Movement, Septuple, Transition},
+        symbol::{self, Symbol},
+        Acceptance, Machine,
+    },
+    playback::{Playback, PlaybackMode},
+};
 
 /// Divide a tela em quatro sub-áreas da área passada, sendo elas duas pequenas fitas no
 /// topo, uma área para a sétupla no meio, e uma área para ajuda abaixo.
@@ -18,46 +28,20 @@ pub fn four_split(area: Rect) -> Vec<Rect> {
         .margin(1)
         .constraints(
             [
-                Constraint::Length(3),
+                // a fita ativa exibe os índices das células abaixo dos símbolos
+                Constraint::Length(4),
                 Constraint::Length(3),
                 Constraint::Min(0),
-                Constraint::Length(3),
+                // a ajuda da tela de processamento ocupa até duas linhas
+                Constraint::Length(4),
             ]
             .as_ref(),
         )
         .split(area)
 }
 
-/// Cria um [`Spans`] à partir de uma fita e a posição atual. O valor retornado pode ser
-/// usado como um [`Paragraph`](tui::widgets::Paragraph) para exibir o estado da fita em
-/// processamento.
-pub fn active_tape_spans(tape: &[char], current_position: usize) -> Spans<'static> {
-    // tipo de retorno `'static` pois todos Span tem uma String (e não &str)
-    let span_vec: Vec<_> = tape
-        .iter()
-        .enumerate()
-        .flat_map(|(i, c)| {
-            let style = if i % 2 == 0 {
-                Style::default().fg(Color::White)
-            } else {
-                Style::default().fg(Color::Rgb(160, 160, 160))
-            };
-
-            if i == current_position {
-                let left_bracket = Span::styled("[", style);
-                let char = Span::styled(c.to_string(), style);
-                let right_bracket = Span::styled("]", style);
-                return vec![left_bracket, char, right_bracket];
-            } else {
-                return vec![Span::styled(c.to_string(), style)];
-            }
-        })
-        .collect();
-
-    Spans(span_vec)
-}
-
-/// Constrói um parágrafo para exibir a sétupla.
+/// Constrói um parágrafo para exibir os conjuntos da sétupla. As transições são
+/// exibidas à parte, por [`transition_table`].
 pub fn septuple_paragraph(sep: &Septuple) -> Paragraph<'static> {
     // tipo de retorno `'static` pois todos Parágrafos tem uma String (e não &str)
     let alphabet = format!("{:?}", sep.alphabet);
@@ -66,45 +50,286 @@ pub fn septuple_paragraph(sep: &Septuple) -> Paragraph<'static> {
     let states = format!("{:?}", sep.states);
     let initial_state = sep.initial_state.clone();
     let final_states = format!("{:?}", sep.final_states);
-    let mut transition_map = String::new();
 
-    for ((state, symbol), transition) in &sep.transition_map {
-        let movement = match transition.move_to {
-            Some(side) => match side {
-                Movement::R => "R",
-                Movement::L => "L",
-            },
-            None => "-",
-        };
-        let transition = format!(
-            "(δ ({state}, {symbol}) = ({}, {}, {}))\n",
-            transition.next_state, transition.write_symbol, movement
-        );
-        // TODO: verificar performance disso -- muitas strings criadas
-        transition_map += &transition;
-    }
-
-    let sep_string = format!("Alfabeto: {}\nSímbolo branco: {}\nSímbolos de entrada: {}\nEstados: {}\nEstado inicial: {}\nEstados finais: {}\nTransições:\n{}", 
-    alphabet, blank_symbol, input_symbols, states, initial_state, final_states, &transition_map[..]);
+    let sep_string = format!("Alfabeto: {}\nSímbolo branco: {}\nSímbolos de entrada: {}\nEstados: {}\nEstado inicial: {}\nEstados finais: {}\n{}", 
+    alphabet, blank_symbol, input_symbols, states, initial_state, final_states, halting_states(sep));
 
     Paragraph::new(sep_string).wrap(Wrap { trim: true })
 }
 
-/// Retorna o [`Spans`] usado para representar a fita original. Os valores de cada
-/// [`Span`] são *owned*.
-pub fn original_tape_spans(tape: &[char]) -> Spans<'static> {
-    let spans: Vec<_> = tape
-        .iter()
+/// Constrói a tabela de transições da sétupla, ordenada por estado e símbolo como em
+/// [`Septuple::sorted_transitions`]. A linha
+/// da transição `active`, prestes a ser aplicada, é selecionada no [`TableState`]
+/// retornado, o que mantém a linha visível; a linha da transição `previous`, aplicada
+/// por último, é esmaecida. Transições por classe de símbolos mostram a classe lida, e
+/// `=` quando mantêm o símbolo lido.
+pub fn transition_table(
+    sep: &Septuple,
+    active: Option<&Transition>,
+    previous: Option<&Transition>,
+) -> (Table<'static>, TableState) {
+    let is = |transition, other: Option<&Transition>| other.is_some_and(|t| ptr::eq(t, transition));
+    let mut state = TableState::default();
+    let rows: Vec<_> = sep
+        .sorted_transitions()
+        .into_iter()
         .enumerate()
-        .map(|(i, c)| {
-            let style = if i % 2 == 0 {
-                Style::default().fg(Color::White)
+        .map(|(i, (from_state, read_symbol, transition))| {
+            if is(transition, active) {
+                state.select(Some(i));
+            }
+            let style = if is(transition, previous) {
+                Style::default()
+                    .fg(Color::DarkGray)
+                    .add_modifier(Modifier::ITALIC)
             } else {
-                Style::default().fg(Color::Rgb(160, 160, 160))
+                Style::default()
             };
-            Span::styled(c.to_string(), style)
+            Row::new(vec![
+                from_state.clone(),
+                read_symbol.to_string(),
+                transition.next_state.clone(),
+                transition
+                    .write_symbol
+                    .as_ref()
+                    .map_or("=".to_string(), |symbol| symbol.to_string()),
+                Movement::symbol(transition.move_to, "-").to_string(),
+            ])
+            .style(style)
         })
         .collect();
+
+    let header = Row::new(vec!["Estado", "Lê", "Próximo", "Escreve", "Move"])
+        .style(Style::default().add_modifier(Modifier::BOLD));
+    let table = Table::new(rows)
+        .header(header)
+        .block(Block::default().title("Transições").borders(Borders::LEFT))
+        .widths(&[
+            Constraint::Percentage(25),
+            Constraint::Percentage(15),
+            Constraint::Percentage(25),
+            Constraint::Percentage(20),
+            Constraint::Percentage(15),
+        ])
+        .highlight_style(
+            Style::default()
+                .fg(Color::Black)
+                .bg(Color::LightGreen)
+                .add_modifier(Modifier::BOLD),
+        )
+        .highlight_symbol("▶ ");
+    (table, state)
+}
+
+/// Constrói a tabela do histórico de execução da máquina, com uma linha para a
+/// configuração inicial e uma para cada transição aplicada. A linha do passo `selected`
+/// é selecionada no [`TableState`] retornado. Como o histórico pode ser longo, apenas as
+/// linhas que podem ser exibidas em uma área de altura `height` em torno da seleção são
+/// construídas.
+pub fn history_table(
+    machine: &Machine,
+    selected: usize,
+    height: usize,
+) -> (Table<'static>, TableState) {
+    let start = selected.saturating_sub(height);
+    let end = (selected + height).min(machine.steps());
+
+    let mut rows = vec![];
+    if start == 0 {
+        rows.push(Row::new(vec!["0".to_string(), "início".to_string()]));
+    }
+    let first = start.saturating_sub(1);
+    let applied = machine.history_range(first..end).enumerate();
+    for (i, applied) in applied.map(|(n, applied)| (first + n, applied)) {
+        rows.push(Row::new(vec![
+            (i + 1).to_string(),
+            applied.from_state.clone(),
+            applied.read_symbol.to_string(),
+            applied.transition.written(&applied.read_symbol).to_string(),
+            Movement::symbol(applied.transition.move_to, "-").to_string(),
+            applied.transition.next_state.clone(),
+        ]));
+    }
+
+    let mut state = TableState::default();
+    state.select(Some(selected - start));
+
+    let header = Row::new(vec!["Passo", "Estado", "Lê", "Escreve", "Move", "Próximo"])
+        .style(Style::default().add_modifier(Modifier::BOLD));
+    let table = Table::new(rows)
+        .header(header)
+        .block(Block::default().title("Histórico").borders(Borders::LEFT))
+        .widths(&[
+            Constraint::Percentage(17),
+            Constraint::Percentage(20),
+            Constraint::Percentage(12),
+            Constraint::Percentage(17),
+            Constraint::Percentage(12),
+            Constraint::Percentage(20),
+        ])
+        .highlight_style(Style::default().add_modifier(Modifier::REVERSED))
+        .highlight_symbol("▶ ");
+    (table, state)
+}
+
+/// Constrói um parágrafo para exibir a sétupla de uma máquina multifita. As transições
+/// são ordenadas como em [`MultiSeptuple::sorted_transitions`].
+pub fn multi_septuple_paragraph(sep: &MultiSeptuple) -> Paragraph<'static> {
+    let base = &sep.septuple;
+    let mut transition_map = String::new();
+
+    for (state, symbols, transition) in sep.sorted_transitions() {
+        let movements: Vec<_> = transition
+            .move_to
+            .iter()
+            .map(|&movement| Movement::symbol(movement, "-"))
+            .collect();
+        let symbols = symbol::join(symbols);
+        let write_symbols = symbol::join(&transition.write_symbols);
+        transition_map += &format!(
+            "(δ ({state}, {symbols}) = ({}, {write_symbols}, {}))\n",
+            transition.next_state,
+            movements.join("")
+        );
+    }
+
+    let sep_string = format!(
+        "Fitas: {}\nAlfabeto: {:?}\nSímbolo branco: {}\nSímbolos de entrada: {:?}\nEstados: {:?}\nEstado inicial: {}\nEstados finais: {:?}\n{}Transições:\n{}",
+        sep.tapes,
+        base.alphabet,
+        base.blank_symbol,
+        base.input_symbols,
+        base.states,
+        base.initial_state,
+        base.final_states,
+        halting_states(base),
+        transition_map
+    );
+
+    Paragraph::new(sep_string).wrap(Wrap { trim: true })
+}
+
+/// Descreve os estados de rejeição e de parada da sétupla, omitindo os conjuntos vazios.
+fn halting_states(sep: &Septuple) -> String {
+    let mut halting = String::new();
+    if !sep.reject_states.is_empty() {
+        halting += &format!("Estados de rejeição: {:?}\n", sep.reject_states);
+    }
+    if !sep.halt_states.is_empty() {
+        halting += &format!("Estados de parada: {:?}\n", sep.halt_states);
+    }
+    halting
+}
+
+/// Retorna o [`Span`] que descreve o estado de aceitação da máquina, exibido no título
+/// da fita.
+pub fn acceptance_span(acceptance: Acceptance) -> Span<'static> {
+    match acceptance {
+        Acceptance::Accepted => Span::styled(
+            " aceitada",
+            Style::default()
+                .fg(Color::LightBlue)
+                .add_modifier(Modifier::BOLD),
+        ),
+        Acceptance::Rejected(rejection) => Span::styled(
+            format!(" rejeitada ({rejection})"),
+            Style::default().fg(Color::Red).add_modifier(Modifier::BOLD),
+        ),
+        Acceptance::Halted => Span::styled(
+            " parada",
+            Style::default()
+                .fg(Color::LightBlue)
+                .add_modifier(Modifier::BOLD),
+        ),
+        Acceptance::Exhausted => Span::styled(
+            " limite de passos atingido",
+            Style::default()
+                .fg(Color::Yellow)
+                .add_modifier(Modifier::BOLD),
+        ),
+        Acceptance::Looping => Span::styled(
+            " em laço",
+            Style::default()
+                .fg(Color::Yellow)
+                .add_modifier(Modifier::BOLD),
+        ),
+    }
+}
+
+/// Constrói a linha do tempo da tela de processamento, que indica o passo atual em
+/// relação ao maior passo já alcançado.
+pub fn timeline_gauge(steps: usize, furthest: usize) -> Gauge<'static> {
+    let ratio = if furthest == 0 {
+        0.0
+    } else {
+        steps as f64 / furthest as f64
+    };
+    Gauge::default()
+        .ratio(ratio)
+        .label(format!("passo {steps} de {furthest}"))
+        .gauge_style(Style::default().fg(Color::Blue).bg(Color::Black))
+}
+
+/// Constrói o parágrafo de ajuda da tela de processamento da máquina.
+/// Caso `debugging` seja `true`, inclui as teclas da linha do tempo e da tela de pontos
+/// de parada.
+pub fn machine_help_paragraph(playback: &Playback, debugging: bool) -> Paragraph<'static> {
+    let play = match playback.mode() {
+        PlaybackMode::Paused => "reproduzir ",
+        PlaybackMode::Playing | PlaybackMode::ToHalt | PlaybackMode::ToStep(_) => "pausar ",
+    };
+    let mut help_spans = vec![
+        Span::styled("<Esc> ", Style::default().fg(Color::Rgb(255, 140, 0))),
+        Span::from("voltar "),
+        Span::styled("< ⟵ > ", Style::default().fg(Color::Rgb(255, 140, 0))),
+        Span::from("desfazer transição "),
+        Span::styled("< ⟶ > ", Style::default().fg(Color::Rgb(255, 140, 0))),
+        Span::from("aplicar transição "),
+        Span::styled("<Espaço> ", Style::default().fg(Color::Rgb(255, 140, 0))),
+        Span::from(play),
+        Span::styled("<+/-> ", Style::default().fg(Color::Rgb(255, 140, 0))),
+        Span::from(format!("velocidade ({}/s) ", playback.speed())),
+        Span::styled("<Enter> ", Style::default().fg(Color::Rgb(255, 140, 0))),
+        Span::from("executar até parar "),
+        Span::styled("<h/l> ", Style::default().fg(Color::Rgb(255, 140, 0))),
+        Span::from("deslocar fita "),
+        Span::styled("<c> ", Style::default().fg(Color::Rgb(255, 140, 0))),
+        Span::from("centralizar "),
+    ];
+    if debugging {
+        help_spans.extend([
+            Span::styled("<g> ", Style::default().fg(Color::Rgb(255, 140, 0))),
+            Span::from("ir para o passo "),
+            Span::styled("< ↑/↓ > ", Style::default().fg(Color::Rgb(255, 140, 0))),
+            Span::from("histórico "),
+            Span::styled("<r> ", Style::default().fg(Color::Rgb(255, 140, 0))),
+            Span::from("retroceder ao passo selecionado "),
+            Span::styled("<b> ", Style::default().fg(Color::Rgb(255, 140, 0))),
+            Span::from("pontos de parada "),
+        ]);
+    }
+    Paragraph::new(Spans(help_spans))
+        .wrap(Wrap { trim: true })
+        .block(help_block())
+}
+
+/// Retorna o [`Spans`] usado para representar a fita original. Os valores de cada
+/// [`Span`] são *owned*. Caso algum símbolo tenha mais de um caractere, os símbolos são
+/// separados por espaços.
+pub fn original_tape_spans(tape: &[Symbol]) -> Spans<'static> {
+    let separated = !tape.iter().all(Symbol::is_char);
+    let mut spans = vec![];
+    for (i, symbol) in tape.iter().enumerate() {
+        if separated && i > 0 {
+            spans.push(Span::raw(" "));
+        }
+        let style = if i % 2 == 0 {
+            Style::default().fg(Color::White)
+        } else {
+            Style::default().fg(Color::Rgb(160, 160, 160))
+        };
+        spans.push(Span::styled(symbol.to_string(), style));
+    }
     spans.into()
 }
 
@@ -0,0 +1,140 @@
+//! Esse submódulo de [`draw`](super) define o widget usado para exibir a fita ativa de
+//! uma máquina. Como a fita pode ser maior que a tela, apenas uma janela em torno da
+//! cabeça é desenhada.
+
+use tui::{
+    buffer::Buffer,
+    layout::Rect,
+    style::{Color, Modifier, Style},
+    widgets::{Block, Widget},
+};
+
+use crate::machine::symbol::Symbol;
+
+/// Widget que exibe uma janela da fita centralizada na cabeça, com o índice de cada
+/// célula abaixo de seu símbolo. Caso existam símbolos além das extremidades da janela,
+/// um indicador é exibido no respectivo lado.
+pub struct TapeView<'t> {
+    tape: &'t [Symbol],
+    /// Índice da cabeça em `tape`.
+    head: usize,
+    /// Índice, em `tape`, da primeira posição da fita original. Os índices exibidos
+    /// são relativos a essa posição.
+    origin: usize,
+    /// Deslocamento, em células, do centro da janela em relação à cabeça.
+    pan: isize,
+    block: Option<Block<'t>>,
+}
+
+impl<'t> TapeView<'t> {
+    pub fn new(tape: &'t [Symbol], head: usize, origin: usize) -> TapeView<'t> {
+        TapeView {
+            tape,
+            head,
+            origin,
+            pan: 0,
+            block: None,
+        }
+    }
+
+    /// Desloca o centro da janela em `pan` células em relação à cabeça.
+    pub fn pan(mut self, pan: isize) -> TapeView<'t> {
+        self.pan = pan;
+        self
+    }
+
+    pub fn block(mut self, block: Block<'t>) -> TapeView<'t> {
+        self.block = Some(block);
+        self
+    }
+
+    /// Retorna o índice da primeira célula visível e o número de células visíveis,
+    /// sendo `cells` o número máximo de células que cabem na área do widget.
+    fn window(&self, cells: usize) -> (usize, usize) {
+        let len = self.tape.len();
+        if len <= cells {
+            return (0, len);
+        }
+        let center = (self.head as isize + self.pan).clamp(0, len as isize - 1) as usize;
+        let start = center.saturating_sub(cells / 2).min(len - cells);
+        (start, cells)
+    }
+
+    /// Retorna o índice exibido para a célula `index` de `tape`.
+    fn label(&self, index: usize) -> String {
+        (index as isize - self.origin as isize).to_string()
+    }
+}
+
+impl Widget for TapeView<'_> {
+    fn render(mut self, area: Rect, buf: &mut Buffer) {
+        let area = match self.block.take() {
+            Some(block) => {
+                let inner = block.inner(area);
+                block.render(area, buf);
+                inner
+            }
+            None => area,
+        };
+        // uma coluna de cada lado é reservada para os indicadores
+        if area.width < 5 || area.height == 0 || self.tape.is_empty() {
+            return;
+        }
+
+        // os índices mais largos estão nas extremidades da fita
+        let widest = self
+            .label(0)
+            .len()
+            .max(self.label(self.tape.len() - 1).len());
+        // a célula da cabeça envolve o símbolo em colchetes
+        let longest = self
+            .tape
+            .iter()
+            .map(|symbol| symbol.as_str().chars().count() + 2)
+            .max()
+            .unwrap_or(3);
+        let cell_width = widest.max(longest).max(3) + 1;
+        let cells = ((area.width - 2) as usize / cell_width).max(1);
+        let (start, count) = self.window(cells);
+
+        let elided = Style::default().fg(Color::Yellow);
+        if start > 0 {
+            buf.set_string(area.x, area.y, "◀", elided);
+        }
+        if start + count < self.tape.len() {
+            buf.set_string(area.right() - 1, area.y, "▶", elided);
+        }
+
+        for (n, i) in (start..start + count).enumerate() {
+            let x = area.x + 1 + (n * cell_width) as u16;
+            let mut style = if i % 2 == 0 {
+                Style::default().fg(Color::White)
+            } else {
+                Style::default().fg(Color::Rgb(160, 160, 160))
+            };
+            let symbol = if i == self.head {
+                style = style.add_modifier(Modifier::BOLD);
+                format!("[{}]", self.tape[i])
+            } else {
+                self.tape[i].to_string()
+            };
+            set_centered(buf, x, area.y, cell_width - 1, &symbol, style);
+
+            if area.height > 1 {
+                let style = if i == self.head {
+                    Style::default().fg(Color::Yellow)
+                } else {
+                    Style::default().fg(Color::DarkGray)
+                };
+                set_centered(buf, x, area.y + 1, cell_width - 1, &self.label(i), style);
+            }
+        }
+    }
+}
+
+/// Escreve `text` centralizado em uma região de `width` colunas, iniciada em `x`.
+fn set_centered(buf: &mut Buffer, x: u16, y: u16, width: usize, text: &str, style: Style) {
+    let len = text.chars().count();
+    let offset = width.saturating_sub(len) / 2;
+    buf.set_string(x + offset as u16, y, text, style);
+}
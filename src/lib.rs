@@ -1,5 +1,7 @@
+pub mod batch;
 pub mod draw;
 pub mod machine;
+pub mod playback;
 pub mod state;
 
 use std::{error, result};
@@ -1,29 +1,126 @@
 #[cfg(test)]
 mod test;
 
+pub mod breakpoint;
+pub mod format;
+pub mod multi;
+pub mod nondet;
 pub mod sep;
-
-use std::fmt::Display;
-
-use self::sep::{Movement, Septuple, Transition, TransitionKey};
-
-/// Uma Máquina de Turing, finita à esquerda.
+pub mod symbol;
+pub mod trace;
+
+use std::{
+    collections::{hash_map::DefaultHasher, HashMap},
+    fmt::Display,
+    hash::{Hash, Hasher},
+    ops::Range,
+};
+
+use self::{
+    sep::{Movement, Septuple, Transition},
+    symbol::Symbol,
+    trace::Trace,
+};
+
+/// Uma Máquina de Turing, finita à esquerda, a não ser que a sétupla defina uma fita
+/// infinita em ambas as direções.
 #[derive(Debug, Clone)]
 pub struct Machine<'machine> {
     septuple: &'machine Septuple,
 
+    /// Índice da cabeça em `tape`.
     current_position: usize,
     current_state: &'machine String,
-    tape: Vec<char>,
+    /// As células da fita, precedidas por `start` brancos reservados para o crescimento
+    /// à esquerda, que não fazem parte da fita.
+    tape: Vec<Symbol>,
+    /// Índice, em `tape`, da primeira célula da fita.
+    start: usize,
+    /// Índice, em `tape`, da primeira posição da fita original. Só é diferente de `start`
+    /// caso a fita tenha crescido para a esquerda.
+    origin: usize,
 
     undos: Vec<Undo<'machine>>,
+
+    /// Número máximo de transições a serem aplicadas. Caso [`None`], não há limite.
+    step_limit: Option<usize>,
+    /// Impressão da fita, atualizada a cada símbolo escrito; veja
+    /// [`Machine::cell_fingerprint`].
+    tape_fingerprint: Fingerprint,
+    /// Impressões das configurações já visitadas, mapeadas aos passos em que ocorreram,
+    /// em ordem crescente. Caso [`None`], a detecção de laços está desativada.
+    visited: Option<HashMap<Fingerprint, Vec<usize>>>,
 }
 
+/// Limite de passos sugerido para execuções em que o usuário não define um limite.
+pub const DEFAULT_STEP_LIMIT: usize = 100_000;
+
 /// Define aceitação ou rejeição de uma fita para uma Máquina de Turing.
 #[derive(Clone, Copy, Debug, PartialEq, Eq)]
 pub enum Acceptance {
     Accepted,
-    Rejected,
+    /// A máquina rejeitou a fita, pelo motivo indicado.
+    Rejected(Rejection),
+    /// A máquina parou em um estado de parada, sem aceitar ou rejeitar a fita. Usado por
+    /// máquinas que computam funções.
+    Halted,
+    /// A máquina atingiu o limite de passos sem parar.
+    Exhausted,
+    /// A máquina repetiu uma configuração anterior e, portanto, nunca irá parar.
+    Looping,
+}
+
+impl Acceptance {
+    /// Retorna `true` caso a máquina tenha parado, isso é, caso tenha aceitado ou
+    /// rejeitado a fita ou alcançado um estado de parada.
+    pub fn halted(self) -> bool {
+        matches!(
+            self,
+            Acceptance::Accepted | Acceptance::Rejected(_) | Acceptance::Halted
+        )
+    }
+}
+
+/// O motivo pelo qual uma máquina rejeitou uma fita.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Rejection {
+    /// A máquina alcançou um estado de rejeição declarado na sétupla.
+    RejectState,
+    /// Não há transição para o estado e símbolo atuais.
+    NoTransition,
+    /// A máquina tentou mover a cabeça para a esquerda da primeira posição de uma fita
+    /// finita à esquerda.
+    LeftEnd,
+}
+
+impl Display for Rejection {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Rejection::RejectState => write!(f, "estado de rejeição"),
+            Rejection::NoTransition => write!(f, "sem transição"),
+            Rejection::LeftEnd => write!(f, "fim da fita à esquerda"),
+        }
+    }
+}
+
+/// Define a região da fita considerada como resultado de uma função computada pela
+/// máquina.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum OutputMode {
+    /// Toda a fita, desconsiderando os símbolos brancos nas extremidades.
+    Trimmed,
+    /// Da posição da cabeça até o primeiro símbolo branco, exclusivo.
+    FromHead,
+}
+
+/// Uma transição aplicada pela máquina, junto do estado e símbolo que a originaram.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct AppliedTransition<'t> {
+    /// Estado da máquina antes da transição.
+    pub from_state: &'t String,
+    /// Símbolo lido na fita antes da transição.
+    pub read_symbol: Symbol,
+    pub transition: &'t Transition,
 }
 
 /// Representa um erro retornado quando uma nova máquina possui uma fita com símbolos
@@ -52,10 +149,16 @@ impl std::error::Error for NoUndoError {}
 
 impl Machine<'_> {
     /// Inicializa uma nova Máquina de Turing. É assumido que `septuple` já foi validada.
+    /// Os símbolos da fita podem ser dados como [`Symbol`] ou, em máquinas cujos símbolos
+    /// têm um único caractere, como `char`.
     ///
     /// # Erros
     /// Retorna um erro caso a fita possua símbolos não contidos no alfabeto.
-    pub fn new(septuple: &Septuple, mut tape: Vec<char>) -> Result<Machine, InvalidSymbolError> {
+    pub fn new(
+        septuple: &Septuple,
+        tape: impl IntoIterator<Item = impl Into<Symbol>>,
+    ) -> Result<Machine<'_>, InvalidSymbolError> {
+        let mut tape: Vec<Symbol> = tape.into_iter().map(Into::into).collect();
         let has_invalid_symbol = tape
             .iter()
             .any(|symbol| !septuple.input_symbols.contains(symbol));
@@ -64,47 +167,96 @@ impl Machine<'_> {
         }
 
         if tape.is_empty() {
-            tape.push(septuple.blank_symbol);
+            tape.push(septuple.blank_symbol.clone());
         }
 
-        Ok(Machine {
+        let mut machine = Machine {
             tape,
             septuple,
             current_position: 0,
+            start: 0,
+            origin: 0,
             current_state: &septuple.initial_state,
             undos: vec![],
-        })
+            step_limit: None,
+            tape_fingerprint: (0, 0),
+            visited: None,
+        };
+        for (position, symbol) in machine.tape.iter().enumerate() {
+            let cell = machine.cell_fingerprint(position as isize, symbol);
+            machine.tape_fingerprint = add(machine.tape_fingerprint, cell);
+        }
+        Ok(machine)
+    }
+
+    /// Define o número máximo de transições que a máquina pode aplicar. Ao atingir o
+    /// limite, a máquina passa a retornar [`Acceptance::Exhausted`].
+    pub fn set_step_limit(&mut self, step_limit: Option<usize>) {
+        self.step_limit = step_limit;
+    }
+
+    /// Ativa ou desativa a detecção de laços. Com a detecção ativa, a máquina retorna
+    /// [`Acceptance::Looping`] ao repetir exatamente uma configuração (estado, posição e
+    /// fita). Configurações anteriores à ativação não são consideradas.
+    pub fn set_loop_detection(&mut self, enabled: bool) {
+        if !enabled {
+            self.visited = None;
+            return;
+        }
+        if self.visited.is_none() {
+            let mut visited = HashMap::new();
+            visited.insert(self.configuration(), vec![self.steps()]);
+            self.visited = Some(visited);
+        }
     }
 
     /// Aplica a transição adequada para o estado atual da máquina. Se o estado atual
     /// indicar uma aceitação ou rejeição, retorna [`Some`]. Caso a máquina ainda esteja
     /// processando a fita, retorna [`None`].
     pub fn transition(&mut self) -> Option<Acceptance> {
-        if self.in_final_state() {
-            return Some(Acceptance::Accepted);
-        }
-
-        let transition = match self.get_transition() {
-            Some(transition) => transition,
-            None => return Some(Acceptance::Rejected), // não há transições para o estado atual
-        };
-
-        if self.limited_left(transition) {
-            return Some(Acceptance::Rejected);
+        if let Some(acceptance) = self.acceptance() {
+            return Some(acceptance);
         }
 
+        // `acceptance` retorna `None` apenas quando há uma transição aplicável
+        let transition = self.get_transition().unwrap();
         let undo = self.apply(transition);
         self.undos.push(undo);
+
+        if self.visited.is_some() {
+            let steps = self.steps();
+            let configuration = self.configuration();
+            if let Some(visited) = &mut self.visited {
+                visited.entry(configuration).or_default().push(steps);
+            }
+        }
         None
     }
 
     /// Desfaz a última transição aplicada na máquina.
     /// Caso não haja transição para ser desfeita, retornar [`Err`].
     pub fn undo_transition(&mut self) -> Result<(), NoUndoError> {
-        let undo = match self.undos.pop() {
-            Some(undo) => undo,
-            None => return Err(NoUndoError),
-        };
+        if self.undos.is_empty() {
+            return Err(NoUndoError);
+        }
+
+        if self.visited.is_some() {
+            let steps = self.steps();
+            let configuration = self.configuration();
+            if let Some(visited) = &mut self.visited {
+                if let Some(steps_visited) = visited.get_mut(&configuration) {
+                    if steps_visited.last() == Some(&steps) {
+                        steps_visited.pop();
+                    }
+                    if steps_visited.is_empty() {
+                        visited.remove(&configuration);
+                    }
+                }
+            }
+        }
+
+        // o vetor não está vazio; unwrap é seguro
+        let undo = self.undos.pop().unwrap();
         if undo.pop {
             self.tape.pop();
         }
@@ -114,33 +266,137 @@ impl Machine<'_> {
                 Movement::L => self.current_position -= 1,
             }
         }
-        self.tape[self.current_position] = undo.write;
+        if undo.shift {
+            self.start += 1;
+        }
+        self.write(undo.write.clone());
         self.current_state = undo.state;
 
         Ok(())
     }
 
+    /// Aplica transições até a máquina parar ou atingir algum de seus limites.
+    pub fn run(&mut self) -> Acceptance {
+        loop {
+            if let Some(acceptance) = self.transition() {
+                return acceptance;
+            }
+        }
+    }
+
+    /// Leva a máquina ao passo `step`, aplicando transições caso ele seja posterior ao
+    /// passo atual ou desfazendo-as caso seja anterior. Retorna o passo alcançado, que é
+    /// menor que `step` caso a máquina pare ou atinja algum de seus limites antes.
+    pub fn seek(&mut self, step: usize) -> usize {
+        while self.steps() > step {
+            // há transições a desfazer enquanto o número de passos é positivo
+            self.undo_transition().unwrap();
+        }
+        while self.steps() < step {
+            if self.transition().is_some() {
+                break;
+            }
+        }
+        self.steps()
+    }
+
+    /// Extrai da fita o resultado da função computada pela máquina, de acordo com
+    /// `mode`. O resultado só é significativo após a máquina parar.
+    pub fn output(&self, mode: OutputMode) -> Vec<Symbol> {
+        let blank = &self.septuple.blank_symbol;
+        match mode {
+            OutputMode::Trimmed => {
+                let tape = self.tape();
+                let start = tape.iter().position(|symbol| symbol != blank);
+                let end = tape.iter().rposition(|symbol| symbol != blank);
+                match (start, end) {
+                    (Some(start), Some(end)) => tape[start..=end].to_vec(),
+                    _ => vec![],
+                }
+            }
+            OutputMode::FromHead => self.tape[self.current_position..]
+                .iter()
+                .take_while(|&symbol| symbol != blank)
+                .cloned()
+                .collect(),
+        }
+    }
+
     /// Retorna o estado de aceitação de máquina. Caso seja [`None`], a máquina ainda
     /// está em processamento.
+    ///
+    /// Estados finais, de rejeição e de parada encerram o processamento mesmo que haja
+    /// transições partindo deles. Aceitação e rejeição têm precedência sobre
+    /// [`Acceptance::Looping`], que por sua vez tem precedência sobre
+    /// [`Acceptance::Exhausted`].
     pub fn acceptance(&self) -> Option<Acceptance> {
-        if self.in_final_state() {
-            return Some(Acceptance::Accepted);
+        if let Some(acceptance) = self.septuple.halting_acceptance(self.current_state) {
+            return Some(acceptance);
         }
         let transition = match self.get_transition() {
             Some(transition) => transition,
-            None => return Some(Acceptance::Rejected),
+            None => return Some(Acceptance::Rejected(Rejection::NoTransition)),
         };
         if self.limited_left(transition) {
-            return Some(Acceptance::Rejected);
+            return Some(Acceptance::Rejected(Rejection::LeftEnd));
+        }
+        if self.looping() {
+            return Some(Acceptance::Looping);
+        }
+        if let Some(step_limit) = self.step_limit {
+            if self.steps() >= step_limit {
+                return Some(Acceptance::Exhausted);
+            }
         }
         None
     }
 
+    /// Retorna `true` caso a detecção de laços esteja ativa e a configuração atual já
+    /// tenha ocorrido em um passo anterior. A impressão da configuração serve apenas como
+    /// filtro: a repetição só é confirmada comparando as configurações completas; veja
+    /// [`Machine::repeats`].
+    fn looping(&self) -> bool {
+        let steps = match &self.visited {
+            Some(visited) => match visited.get(&self.configuration()) {
+                Some(steps) => steps,
+                None => return false,
+            },
+            None => return false,
+        };
+        steps
+            .iter()
+            .take_while(|&&step| step < self.steps())
+            .any(|&step| self.repeats(step))
+    }
+
+    /// Retorna `true` caso a configuração atual (estado, posição e fita) seja igual à do
+    /// passo `step`, anterior ao atual. A configuração do passo `step` é reconstruída a
+    /// partir da atual, desfazendo sobre uma cópia da fita as transições aplicadas desde
+    /// então.
+    fn repeats(&self, step: usize) -> bool {
+        let mut tape = self.tape.clone();
+        let mut position = self.current_position;
+        let mut state = self.current_state;
+        for undo in self.undos[step..].iter().rev() {
+            match undo.movement {
+                Some(Movement::R) => position += 1,
+                Some(Movement::L) => position -= 1,
+                None => {}
+            }
+            // células criadas após `step` são brancas em `step`, e brancos reservados à
+            // esquerda permanecem em `tape`; as fitas podem ser comparadas por inteiro
+            tape[position] = undo.write.clone();
+            state = undo.state;
+        }
+        state == self.current_state && position == self.current_position && tape == self.tape
+    }
+
     /// Retorna `true` caso a máquina esteja atualmente "limitada pela esquerda", isso é,
     /// caso ela esteja na posição zero da fita e tenha como próxima etapa uma transição
-    /// com movimento para a esquerda.
+    /// com movimento para a esquerda. Máquinas com fita infinita em ambas as direções
+    /// nunca são limitadas.
     fn limited_left(&self, transition: &Transition) -> bool {
-        if self.current_position == 0 {
+        if !self.septuple.two_way_tape && self.current_position == self.start {
             let movement = match transition.move_to {
                 Some(movement) => movement,
                 None => return false,
@@ -152,65 +408,150 @@ impl Machine<'_> {
         false
     }
 
-    /// Retorna `true` se a máquina estiver em um estado final.
-    fn in_final_state(&self) -> bool {
-        self.septuple.final_states.contains(self.current_state)
-    }
-
     // --- getters
 
     pub fn septuple(&self) -> &Septuple {
         self.septuple
     }
 
-    pub fn current_position(&self) -> usize {
-        self.current_position
+    /// Retorna a posição da cabeça relativa ao início da fita original. A posição só é
+    /// negativa em fitas infinitas em ambas as direções.
+    pub fn current_position(&self) -> isize {
+        self.current_position as isize - self.origin as isize
+    }
+
+    /// Retorna o índice da cabeça em [`Machine::tape`].
+    pub fn head_index(&self) -> usize {
+        self.current_position - self.start
+    }
+
+    /// Retorna o índice, em [`Machine::tape`], da primeira posição da fita original.
+    pub fn origin(&self) -> usize {
+        self.origin - self.start
     }
 
     pub fn current_state(&self) -> &String {
         self.current_state
     }
 
-    pub fn tape(&self) -> &Vec<char> {
-        &self.tape
+    pub fn tape(&self) -> &[Symbol] {
+        &self.tape[self.start..]
+    }
+
+    /// Retorna o número de transições aplicadas até o momento.
+    pub fn steps(&self) -> usize {
+        self.undos.len()
     }
 }
 
 impl<'machine> Machine<'machine> {
-    /// Retorna, caso exista, a transição para o estado atual da máquina.
+    /// Retorna um iterador que, a cada item, aplica uma transição e retorna a
+    /// configuração resultante. O primeiro item é a configuração atual e o iterador
+    /// termina quando a máquina para ou atinge algum de seus limites.
+    pub fn trace(&mut self) -> Trace<'_, 'machine> {
+        Trace::new(self)
+    }
+
+    /// Retorna as transições aplicadas até o momento, da primeira à última.
+    pub fn history(&self) -> impl DoubleEndedIterator<Item = AppliedTransition<'machine>> + '_ {
+        self.undos.iter().map(Undo::applied)
+    }
+
+    /// Retorna as transições aplicadas nos passos de `range`, sendo a transição de índice
+    /// `i` a que levou ao passo `i + 1`. O intervalo é limitado ao número de passos.
+    pub fn history_range(
+        &self,
+        range: Range<usize>,
+    ) -> impl DoubleEndedIterator<Item = AppliedTransition<'machine>> + '_ {
+        let end = range.end.min(self.undos.len());
+        let start = range.start.min(end);
+        self.undos[start..end].iter().map(Undo::applied)
+    }
+
+    /// Retorna a última transição aplicada, caso exista, sem percorrer o histórico.
+    pub fn last_applied(&self) -> Option<AppliedTransition<'machine>> {
+        self.undos.last().map(Undo::applied)
+    }
+
+    /// Retorna a impressão da configuração atual da máquina (estado, posição e fita).
+    /// Como brancos não contribuem para a impressão da fita, brancos nas extremidades são
+    /// desconsiderados, já que a fita é preenchida com brancos à medida que a máquina
+    /// avança. A fita não é percorrida nem copiada.
+    fn configuration(&self) -> Fingerprint {
+        let configuration = (
+            self.current_state,
+            self.current_position(),
+            self.tape_fingerprint,
+        );
+        fingerprint(&configuration)
+    }
+
+    /// Retorna a contribuição de `symbol`, na posição `position` relativa ao início da
+    /// fita original, para a impressão da fita, que é a soma das contribuições de todas
+    /// as células. Brancos não contribuem.
+    fn cell_fingerprint(&self, position: isize, symbol: &Symbol) -> Fingerprint {
+        if *symbol == self.septuple.blank_symbol {
+            (0, 0)
+        } else {
+            fingerprint(&(position, symbol))
+        }
+    }
+
+    /// Escreve `symbol` na posição da cabeça, atualizando a impressão da fita. Retorna o
+    /// símbolo sobrescrito.
+    fn write(&mut self, symbol: Symbol) -> Symbol {
+        let position = self.current_position();
+        let added = self.cell_fingerprint(position, &symbol);
+        let previous = std::mem::replace(&mut self.tape[self.current_position], symbol);
+        let removed = self.cell_fingerprint(position, &previous);
+        self.tape_fingerprint = sub(add(self.tape_fingerprint, added), removed);
+        previous
+    }
+
+    /// Retorna, caso exista, a transição para o estado atual da máquina. Transições
+    /// exatas têm precedência sobre transições por classe de símbolos; veja
+    /// [`Septuple::find_transition`].
     fn get_transition(&self) -> Option<&'machine Transition> {
-        let current_symbol = self.tape[self.current_position];
-        let transition_key = (self.current_state, &current_symbol);
+        let current_symbol = &self.tape[self.current_position];
         self.septuple
-            .transition_map
-            .get(&transition_key as &dyn TransitionKey)
+            .find_transition(self.current_state, current_symbol)
     }
 
     /// Aplica a transição encontrada e retorna um [`Undo`] equivalente.
     ///
-    /// Esse método não deve ser chamado quando a fita estiver na posição 0 e a transição
-    /// representar um movimento à esquerda, pois isso causará um underflow no atributo
-    /// `current_position` da máquina.
+    /// Esse método não deve ser chamado quando a máquina estiver limitada pela esquerda,
+    /// pois isso causará um underflow no atributo `current_position` da máquina. Em fitas
+    /// infinitas em ambas as direções, a fita cresce à esquerda usando os brancos
+    /// reservados; veja [`grow_left`].
     fn apply(&mut self, transition: &'machine Transition) -> Undo<'machine> {
         // inicializa as variáveis de construção do Undo
         let mut undo_pop = false;
+        let mut undo_shift = false;
         let mut undo_movement: Option<Movement> = None;
-        let undo_write = self.tape[self.current_position];
         let undo_state = self.current_state;
-
-        self.tape[self.current_position] = transition.write_symbol;
+        let undo_write = self.write(transition.written(&self.tape[self.current_position]));
         self.current_state = &transition.next_state;
         match transition.move_to {
             Some(movement) => match movement {
                 Movement::R => {
                     if self.current_position == self.tape.len() - 1 {
                         undo_pop = true;
-                        self.tape.push(self.septuple.blank_symbol);
+                        self.tape.push(self.septuple.blank_symbol.clone());
                     }
                     undo_movement = Some(Movement::L);
                     self.current_position += 1;
                 }
                 Movement::L => {
+                    if self.current_position == self.start {
+                        if self.start == 0 {
+                            let reserved = grow_left(&mut self.tape, &self.septuple.blank_symbol);
+                            self.current_position += reserved;
+                            self.start += reserved;
+                            self.origin += reserved;
+                        }
+                        undo_shift = true;
+                        self.start -= 1;
+                    }
                     undo_movement = Some(Movement::R);
                     self.current_position -= 1;
                 }
@@ -220,9 +561,11 @@ impl<'machine> Machine<'machine> {
 
         Undo {
             pop: undo_pop,
+            shift: undo_shift,
             movement: undo_movement,
             write: undo_write,
             state: undo_state,
+            transition,
         }
     }
 }
@@ -234,10 +577,61 @@ impl<'machine> Machine<'machine> {
 struct Undo<'u> {
     /// Caso `true`, um símbolo branco foi adicionado na última transição.
     pop: bool,
+    /// Caso `true`, a fita cresceu uma célula à esquerda na última transição. A célula
+    /// volta a ser reservada **após** desfazer o último movimento.
+    shift: bool,
     /// Caso `Some`, é o movimento oposto da última transição.
     movement: Option<Movement>,
-    /// Indica o char a ser escrito na fita **após** desfazer o último movimento.
-    write: char,
+    /// Indica o símbolo a ser escrito na fita **após** desfazer o último movimento.
+    write: Symbol,
     /// Indica o estado da máquina antes da última transição.
     state: &'u String,
+    /// A transição que foi aplicada.
+    transition: &'u Transition,
+}
+
+impl<'u> Undo<'u> {
+    /// Retorna a transição desfeita por esse `Undo`.
+    fn applied(&self) -> AppliedTransition<'u> {
+        AppliedTransition {
+            from_state: self.state,
+            read_symbol: self.write.clone(),
+            transition: self.transition,
+        }
+    }
+}
+
+/// Reserva brancos à esquerda de `tape`, em quantidade proporcional ao seu tamanho, e
+/// retorna quantos foram reservados. Como cada reserva ao menos dobra a fita, mover a
+/// cabeça para a esquerda custa tempo constante amortizado.
+pub(crate) fn grow_left(tape: &mut Vec<Symbol>, blank: &Symbol) -> usize {
+    let reserved = tape.len().max(MIN_RESERVED);
+    tape.splice(0..0, std::iter::repeat_n(blank.clone(), reserved));
+    reserved
+}
+
+/// Número mínimo de brancos reservados por [`grow_left`].
+const MIN_RESERVED: usize = 16;
+
+/// Impressão de uma configuração instantânea da máquina, usada na detecção de laços como
+/// filtro antes da comparação das configurações completas. São dois hashes
+/// independentes, o que torna colisões raras, mas não impossíveis.
+type Fingerprint = (u64, u64);
+
+fn fingerprint<T: Hash>(value: &T) -> Fingerprint {
+    let hash = |seed: u8| {
+        let mut hasher = DefaultHasher::new();
+        seed.hash(&mut hasher);
+        value.hash(&mut hasher);
+        hasher.finish()
+    };
+    (hash(0), hash(1))
+}
+
+fn add(a: Fingerprint, b: Fingerprint) -> Fingerprint {
+    (a.0.wrapping_add(b.0), a.1.wrapping_add(b.1))
+}
+
+fn sub(a: Fingerprint, b: Fingerprint) -> Fingerprint {
+    (a.0.wrapping_sub(b.0), a.1.wrapping_sub(b.1))
 }
@@ -0,0 +1,141 @@
+//! Pontos de parada, usados para interromper a execução automática de uma [`Machine`]
+//! ao alcançar uma configuração específica.
+
+use std::{fmt::Display, str::FromStr};
+
+use super::{sep::Septuple, symbol, Machine};
+
+/// Uma condição que interrompe a execução automática da máquina ao ser satisfeita.
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub enum Breakpoint {
+    /// A máquina está no estado indicado.
+    State(String),
+    /// A máquina está no estado indicado e lê o símbolo indicado.
+    Symbol {
+        state: String,
+        symbol: symbol::Symbol,
+    },
+    /// A máquina aplicou exatamente o número indicado de transições.
+    Step(usize),
+    /// A cabeça está na posição indicada, relativa ao início da fita original.
+    Position(isize),
+}
+
+impl Breakpoint {
+    /// Retorna `true` caso a configuração atual de `machine` satisfaça o ponto de parada.
+    pub fn hit(&self, machine: &Machine) -> bool {
+        match self {
+            Breakpoint::State(state) => machine.current_state() == state,
+            Breakpoint::Symbol { state, symbol } => {
+                machine.current_state() == state && machine.tape()[machine.head_index()] == *symbol
+            }
+            Breakpoint::Step(step) => machine.steps() == *step,
+            Breakpoint::Position(position) => machine.current_position() == *position,
+        }
+    }
+
+    /// Verifica se o estado e o símbolo do ponto de parada pertencem a `septuple`. Caso
+    /// contrário, o ponto de parada nunca seria alcançado.
+    pub fn valid(&self, septuple: &Septuple) -> Result<(), InvalidBreakpointError> {
+        let (state, symbol) = match self {
+            Breakpoint::State(state) => (state, None),
+            Breakpoint::Symbol { state, symbol } => (state, Some(symbol)),
+            Breakpoint::Step(_) | Breakpoint::Position(_) => return Ok(()),
+        };
+        if !septuple.states.contains(state) {
+            return Err(InvalidBreakpointError::UnknownState(state.clone()));
+        }
+        match symbol {
+            Some(symbol) if !septuple.alphabet.contains(symbol) => {
+                Err(InvalidBreakpointError::UnknownSymbol(symbol.clone()))
+            }
+            _ => Ok(()),
+        }
+    }
+
+    /// Retorna uma descrição legível do ponto de parada.
+    pub fn describe(&self) -> String {
+        match self {
+            Breakpoint::State(state) => format!("estado {state}"),
+            Breakpoint::Symbol { state, symbol } => format!("estado {state} lendo {symbol}"),
+            Breakpoint::Step(step) => format!("passo {step}"),
+            Breakpoint::Position(position) => format!("posição {position}"),
+        }
+    }
+}
+
+/// Escreve o ponto de parada na mesma sintaxe aceita por [`Breakpoint::from_str`].
+impl Display for Breakpoint {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Breakpoint::State(state) => write!(f, "{state}"),
+            Breakpoint::Symbol { state, symbol } => write!(f, "{state} {symbol}"),
+            Breakpoint::Step(step) => write!(f, "#{step}"),
+            Breakpoint::Position(position) => write!(f, "@{position}"),
+        }
+    }
+}
+
+/// Erro retornado ao interpretar ou validar um [`Breakpoint`] inválido.
+#[derive(Debug, PartialEq, Eq)]
+pub enum InvalidBreakpointError {
+    /// O texto não segue nenhuma das formas aceitas.
+    Syntax(String),
+    /// O estado não pertence à sétupla.
+    UnknownState(String),
+    /// O símbolo não pertence ao alfabeto da sétupla.
+    UnknownSymbol(symbol::Symbol),
+}
+
+impl Display for InvalidBreakpointError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            InvalidBreakpointError::Syntax(s) => write!(f, "ponto de parada inválido: {s}"),
+            InvalidBreakpointError::UnknownState(state) => {
+                write!(
+                    f,
+                    "estado \"{state}\" não está contido no conjunto de estados"
+                )
+            }
+            InvalidBreakpointError::UnknownSymbol(symbol) => {
+                write!(f, "símbolo '{symbol}' não está contido no alfabeto")
+            }
+        }
+    }
+}
+impl std::error::Error for InvalidBreakpointError {}
+
+/// Interpreta um ponto de parada nas formas `<estado>`, `<estado> <símbolo>`,
+/// `#<passo>` e `@<posição>`.
+impl FromStr for Breakpoint {
+    type Err = InvalidBreakpointError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let invalid = || InvalidBreakpointError::Syntax(s.to_string());
+        let s = s.trim();
+
+        if let Some(step) = s.strip_prefix('#') {
+            return step.parse().map(Breakpoint::Step).map_err(|_| invalid());
+        }
+        if let Some(position) = s.strip_prefix('@') {
+            return position
+                .parse()
+                .map(Breakpoint::Position)
+                .map_err(|_| invalid());
+        }
+
+        let mut words = s.split_whitespace();
+        let state = words.next().ok_or_else(invalid)?.to_string();
+        let symbol = match words.next() {
+            Some(symbol) => symbol,
+            None => return Ok(Breakpoint::State(state)),
+        };
+        match words.next() {
+            None => Ok(Breakpoint::Symbol {
+                state,
+                symbol: symbol.into(),
+            }),
+            Some(_) => Err(invalid()),
+        }
+    }
+}
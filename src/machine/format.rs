@@ -0,0 +1,158 @@
+//! Conversão de sétuplas de e para outros formatos além do JSON de definição.
+
+pub mod dot;
+pub mod jflap;
+pub mod morphett;
+pub mod text;
+pub mod tmio;
+
+use std::{fmt::Display, path::Path, str::FromStr};
+
+use super::sep::{LoadError, Septuple, TransitionClash};
+
+/// Os formatos de e para os quais uma [`Septuple`] pode ser convertida.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Format {
+    /// O mesmo JSON aceito por [`Septuple::from_json`].
+    Json,
+    /// YAML com os mesmos campos do JSON (`.yaml` ou `.yml`).
+    Yaml,
+    /// TOML com os mesmos campos do JSON (`.toml`).
+    Toml,
+    /// Diagrama de estados na linguagem DOT, do Graphviz.
+    Dot,
+    /// Arquivo de máquina de Turing do JFLAP (`.jff`).
+    Jflap,
+    /// Formato textual compacto, com uma transição por linha (`.tm`).
+    Text,
+    /// Formato de linhas do simulador de Morphett.
+    Morphett,
+    /// Formato YAML do turingmachine.io.
+    Tmio,
+}
+
+/// Avisos da conversão de uma sétupla, quando parte dela não pode ser representada
+/// exatamente no formato de destino ou precisou ser inferida.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum FormatWarning {
+    /// Duas transições foram declaradas de forma idêntica.
+    Duplicate(TransitionClash),
+    /// O estado inicial do formato não foi encontrado, e o estado indicado foi usado.
+    InitialState {
+        expected: &'static str,
+        used: String,
+    },
+    /// A fita da sétupla é finita à esquerda, mas a do formato é infinita em ambas as
+    /// direções.
+    OneWayTape(Format),
+}
+
+impl Display for FormatWarning {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            FormatWarning::Duplicate(duplicate) => write!(f, "{duplicate} são idênticas"),
+            FormatWarning::InitialState { expected, used } => write!(
+                f,
+                "estado inicial {expected} não encontrado; {used} é usado como estado inicial"
+            ),
+            FormatWarning::OneWayTape(format) => write!(
+                f,
+                "a fita da sétupla é finita à esquerda, mas no formato {format} é infinita em \
+                ambas as direções"
+            ),
+        }
+    }
+}
+
+impl Format {
+    /// Converte `septuple` para o formato, retornando também os avisos sobre o que não
+    /// pôde ser representado exatamente.
+    pub fn export(self, septuple: &Septuple) -> (String, Vec<FormatWarning>) {
+        let two_way_only = matches!(self, Format::Jflap | Format::Morphett | Format::Tmio);
+        let (exported, mut warnings) = match self {
+            Format::Json => (septuple.to_json(), vec![]),
+            Format::Yaml => (septuple.to_yaml(), vec![]),
+            Format::Toml => (septuple.to_toml(), vec![]),
+            Format::Dot => (dot::to_dot(septuple), vec![]),
+            Format::Jflap => (jflap::to_jff(septuple), vec![]),
+            Format::Text => (text::to_text(septuple), vec![]),
+            Format::Morphett => (morphett::to_morphett(septuple), vec![]),
+            Format::Tmio => (tmio::to_tmio(septuple), vec![]),
+        };
+        if two_way_only && !septuple.two_way_tape {
+            warnings.insert(0, FormatWarning::OneWayTape(self));
+        }
+        (exported, warnings)
+    }
+
+    /// Cria uma sétupla à partir de `contents`, no formato, retornando também os avisos
+    /// da importação, como as transições declaradas mais de uma vez de forma idêntica.
+    ///
+    /// # Erros
+    /// Retorna um erro caso o conteúdo não seja uma sétupla válida no formato, caso
+    /// existam transições conflitantes ou caso o formato não possa ser importado.
+    pub fn import(self, contents: &str) -> Result<(Septuple, Vec<FormatWarning>), LoadError> {
+        let (septuple, duplicates) = match self {
+            Format::Json => Septuple::from_json_with_warnings(contents)?,
+            Format::Yaml => Septuple::from_yaml_with_warnings(contents)?,
+            Format::Toml => Septuple::from_toml_with_warnings(contents)?,
+            Format::Jflap => jflap::from_jff(contents)?,
+            Format::Text => text::from_text(contents)?,
+            Format::Morphett => return morphett::from_morphett(contents),
+            Format::Tmio => tmio::from_tmio(contents)?,
+            Format::Dot => return Err(LoadError::Unsupported(self)),
+        };
+        let warnings = duplicates.into_iter().map(FormatWarning::Duplicate);
+        Ok((septuple, warnings.collect()))
+    }
+
+    /// Retorna o formato correspondente à extensão do arquivo em `path`, caso exista.
+    pub fn from_path(path: impl AsRef<Path>) -> Option<Format> {
+        path.as_ref().extension()?.to_str()?.parse().ok()
+    }
+}
+
+/// Escreve o nome do formato, o mesmo aceito por [`Format::from_str`].
+impl Display for Format {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Format::Json => write!(f, "json"),
+            Format::Yaml => write!(f, "yaml"),
+            Format::Toml => write!(f, "toml"),
+            Format::Dot => write!(f, "dot"),
+            Format::Jflap => write!(f, "jff"),
+            Format::Text => write!(f, "tm"),
+            Format::Morphett => write!(f, "morphett"),
+            Format::Tmio => write!(f, "tmio"),
+        }
+    }
+}
+
+/// Erro retornado ao interpretar um [`Format`] desconhecido.
+#[derive(Debug)]
+pub struct UnknownFormatError(String);
+
+impl Display for UnknownFormatError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "formato de sétupla desconhecido: {}", self.0)
+    }
+}
+impl std::error::Error for UnknownFormatError {}
+
+impl FromStr for Format {
+    type Err = UnknownFormatError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "json" => Ok(Format::Json),
+            "yaml" | "yml" => Ok(Format::Yaml),
+            "toml" => Ok(Format::Toml),
+            "dot" => Ok(Format::Dot),
+            "jff" => Ok(Format::Jflap),
+            "tm" => Ok(Format::Text),
+            "morphett" => Ok(Format::Morphett),
+            "tmio" => Ok(Format::Tmio),
+            _ => Err(UnknownFormatError(s.to_string())),
+        }
+    }
+}
@@ -0,0 +1,80 @@
+//! Exportação do diagrama de estados de uma [`Septuple`] para a linguagem DOT, do
+//! [Graphviz](https://graphviz.org/).
+
+use std::fmt::Write;
+
+use indexmap::IndexMap;
+
+use crate::machine::sep::{Movement, Septuple};
+
+/// Gera o diagrama de estados de `septuple` em DOT. O estado inicial é indicado por uma
+/// seta sem origem, estados finais por um círculo duplo, estados de rejeição em
+/// vermelho e estados de parada por um quadrado. Cada aresta é rotulada com
+/// `leitura→escrita,movimento`, com a escrita `=` quando o símbolo lido é mantido e a
+/// leitura na notação de [`SymbolClass`](crate::machine::sep::SymbolClass) para classes
+/// de símbolos. Transições paralelas entre os mesmos estados são agrupadas em uma única
+/// aresta, com um rótulo por linha.
+pub fn to_dot(septuple: &Septuple) -> String {
+    // `write!` em uma `String` nunca falha
+    let mut dot = String::new();
+    writeln!(dot, "digraph {{").unwrap();
+    writeln!(dot, "    rankdir=LR;").unwrap();
+    writeln!(dot, "    node [shape=circle];").unwrap();
+    writeln!(dot, "    __start [shape=point];").unwrap();
+    writeln!(dot, "    __start -> {};", id(&septuple.initial_state)).unwrap();
+
+    for state in &septuple.states {
+        let mut attributes = vec![];
+        if septuple.final_states.contains(state) {
+            attributes.push("shape=doublecircle");
+        } else if septuple.halt_states.contains(state) {
+            attributes.push("shape=square");
+        }
+        if septuple.reject_states.contains(state) {
+            attributes.push("color=red");
+        }
+        if attributes.is_empty() {
+            writeln!(dot, "    {};", id(state)).unwrap();
+        } else {
+            writeln!(dot, "    {} [{}];", id(state), attributes.join(", ")).unwrap();
+        }
+    }
+
+    let mut edges: IndexMap<(&String, &String), Vec<String>> = IndexMap::new();
+    for (from_state, read_symbol, transition) in septuple.sorted_transitions() {
+        let movement = Movement::symbol(transition.move_to, "-");
+        edges
+            .entry((from_state, &transition.next_state))
+            .or_default()
+            .push(format!(
+                "{read_symbol}→{},{movement}",
+                transition
+                    .write_symbol
+                    .as_ref()
+                    .map_or("=".to_string(), |symbol| symbol.to_string())
+            ));
+    }
+    for ((from_state, next_state), labels) in edges {
+        let label = labels.join("\n");
+        writeln!(
+            dot,
+            "    {} -> {} [label={}];",
+            id(from_state),
+            id(next_state),
+            id(&label)
+        )
+        .unwrap();
+    }
+
+    writeln!(dot, "}}").unwrap();
+    dot
+}
+
+/// Escreve `text` como um identificador DOT entre aspas.
+fn id(text: &str) -> String {
+    let escaped = text
+        .replace('\\', "\\\\")
+        .replace('"', "\\\"")
+        .replace('\n', "\\n");
+    format!("\"{escaped}\"")
+}
@@ -0,0 +1,274 @@
+//! Conversão entre sétuplas e o formato XML de máquinas de Turing do
+//! [JFLAP](https://www.jflap.org/) (`.jff`). Apenas máquinas de uma fita, sem blocos,
+//! são suportadas.
+//!
+//! No JFLAP, o símbolo branco é representado por um elemento vazio e a fita é infinita
+//! em ambas as direções. Movimentos `S` mantêm a cabeça na mesma posição. Os símbolos
+//! do JFLAP possuem um único caractere; símbolos com nomes maiores são exportados como
+//! estão, mas não são aceitos pelo JFLAP.
+
+use std::{collections::HashMap, fmt::Display};
+
+use indexmap::IndexSet;
+use roxmltree::{Document, Node};
+
+use crate::machine::{
+    sep::{KeyedTransition, LoadError, Movement, Septuple, Transition, TransitionClash},
+    symbol::Symbol,
+};
+
+/// Símbolo branco usado na sétupla importada, caso não seja usado pela máquina.
+const BLANK: char = 'B';
+/// Símbolo branco alternativo, usado caso a máquina use [`BLANK`] como um símbolo.
+const FALLBACK_BLANK: char = '□';
+
+/// Define os erros que podem ocorrer ao importar um arquivo do JFLAP.
+#[derive(Debug)]
+pub enum JflapError {
+    /// O arquivo não é um XML válido.
+    Xml(roxmltree::Error),
+    /// O arquivo define um autômato que não é uma máquina de Turing.
+    NotTuring(String),
+    /// A máquina possui mais de uma fita.
+    MultiTape,
+    /// A máquina usa blocos, que não são suportados.
+    Blocks,
+    /// Um elemento obrigatório não foi encontrado.
+    Missing(&'static str),
+    /// Uma transição referencia um estado não declarado.
+    UnknownState(String),
+    /// Dois estados, de ids diferentes, possuem o mesmo nome.
+    DuplicateState(String),
+    /// Um símbolo possui mais de um caractere.
+    InvalidSymbol(String),
+    /// Um movimento não é `L`, `R` ou `S`.
+    InvalidMove(String),
+    /// Nenhum estado é marcado como inicial.
+    NoInitialState,
+}
+
+impl Display for JflapError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            JflapError::Xml(err) => write!(f, "XML inválido: {err}"),
+            JflapError::NotTuring(kind) => {
+                write!(
+                    f,
+                    "o arquivo define um autômato do tipo \"{kind}\", e não uma máquina de Turing"
+                )
+            }
+            JflapError::MultiTape => write!(f, "máquinas com mais de uma fita não são suportadas"),
+            JflapError::Blocks => write!(f, "máquinas com blocos não são suportadas"),
+            JflapError::Missing(element) => write!(f, "elemento <{element}> não encontrado"),
+            JflapError::UnknownState(id) => write!(f, "estado de id \"{id}\" não declarado"),
+            JflapError::DuplicateState(name) => {
+                write!(f, "mais de um estado possui o nome \"{name}\"")
+            }
+            JflapError::InvalidSymbol(symbol) => {
+                write!(f, "símbolo \"{symbol}\" possui mais de um caractere")
+            }
+            JflapError::InvalidMove(movement) => write!(f, "movimento \"{movement}\" inválido"),
+            JflapError::NoInitialState => write!(f, "nenhum estado inicial definido"),
+        }
+    }
+}
+impl std::error::Error for JflapError {}
+
+impl From<JflapError> for LoadError {
+    fn from(err: JflapError) -> Self {
+        LoadError::Jflap(err)
+    }
+}
+
+/// Cria uma sétupla à partir de um arquivo do JFLAP, retornando também as transições
+/// declaradas mais de uma vez de forma idêntica. Como o JFLAP não define um alfabeto de
+/// entrada, todos os símbolos das transições, exceto o branco, são considerados
+/// símbolos de entrada.
+///
+/// # Erros
+/// Retorna um erro caso o arquivo não seja uma máquina de Turing do JFLAP suportada, caso
+/// dois estados possuam o mesmo nome ou caso duas transições partam do mesmo estado e
+/// símbolo mas definam ações diferentes.
+pub fn from_jff(xml: &str) -> Result<(Septuple, Vec<TransitionClash>), LoadError> {
+    let document = Document::parse(xml).map_err(JflapError::Xml)?;
+    let structure = document.root_element();
+
+    let kind = child_text(structure, "type").ok_or(JflapError::Missing("type"))?;
+    if kind != "turing" {
+        return Err(JflapError::NotTuring(kind.to_string()).into());
+    }
+    if child_text(structure, "tapes").is_some_and(|tapes| tapes.trim() != "1") {
+        return Err(JflapError::MultiTape.into());
+    }
+    // arquivos antigos não possuem o elemento <automaton>
+    let automaton = child(structure, "automaton").unwrap_or(structure);
+    if child(automaton, "block").is_some() {
+        return Err(JflapError::Blocks.into());
+    }
+
+    let mut names = HashMap::new();
+    let mut states = IndexSet::new();
+    let mut initial_state = None;
+    let mut final_states = IndexSet::new();
+    for state in children(automaton, "state") {
+        let id = state
+            .attribute("id")
+            .ok_or(JflapError::Missing("state id"))?;
+        let name = state
+            .attribute("name")
+            .map_or_else(|| format!("q{id}"), String::from);
+        if child(state, "initial").is_some() {
+            initial_state = Some(name.clone());
+        }
+        if child(state, "final").is_some() {
+            final_states.insert(name.clone());
+        }
+        names.insert(id, name.clone());
+        if !states.insert(name.clone()) {
+            return Err(JflapError::DuplicateState(name).into());
+        }
+    }
+    let initial_state = initial_state.ok_or(JflapError::NoInitialState)?;
+
+    // os símbolos são lidos antes da escolha do branco; `None` representa o branco
+    let mut raw_transitions = vec![];
+    let mut symbols = IndexSet::new();
+    for transition in children(automaton, "transition") {
+        let state = |element| -> Result<String, JflapError> {
+            let id = child_text(transition, element).ok_or(JflapError::Missing(element))?;
+            let id = id.trim();
+            names
+                .get(id)
+                .cloned()
+                .ok_or_else(|| JflapError::UnknownState(id.to_string()))
+        };
+        let from_state = state("from")?;
+        let next_state = state("to")?;
+        let read = symbol(transition, "read")?;
+        let write = symbol(transition, "write")?;
+        let move_to = match child_text(transition, "move").map(str::trim) {
+            Some("R") => Some(Movement::R),
+            Some("L") => Some(Movement::L),
+            Some("S") => None,
+            Some(movement) => return Err(JflapError::InvalidMove(movement.to_string()).into()),
+            None => return Err(JflapError::Missing("move").into()),
+        };
+        symbols.extend(read.into_iter().chain(write));
+        raw_transitions.push((from_state, read, write, next_state, move_to));
+    }
+
+    let blank = if symbols.contains(&BLANK) {
+        FALLBACK_BLANK
+    } else {
+        BLANK
+    };
+    let transitions: Vec<KeyedTransition> = raw_transitions
+        .into_iter()
+        .map(|(from_state, read, write, next_state, move_to)| {
+            (
+                (from_state, read.unwrap_or(blank).into()),
+                Transition {
+                    write_symbol: Some(write.unwrap_or(blank).into()),
+                    next_state,
+                    move_to,
+                },
+            )
+        })
+        .collect();
+
+    let input_symbols: IndexSet<Symbol> = symbols.into_iter().map(Symbol::from).collect();
+    let mut alphabet = input_symbols.clone();
+    alphabet.insert(blank.into());
+    let septuple = Septuple {
+        final_states,
+        two_way_tape: true,
+        ..Septuple::new(alphabet, blank.into(), input_symbols, states, initial_state)
+    };
+    septuple.with_transitions(transitions)
+}
+
+/// Converte a sétupla para o formato do JFLAP. Os estados são dispostos em uma grade, na
+/// ordem de declaração. Estados de rejeição e de parada não possuem equivalente no
+/// JFLAP e são exportados como estados comuns, e transições por classe de símbolos são
+/// expandidas em uma transição por símbolo.
+pub fn to_jff(septuple: &Septuple) -> String {
+    let mut xml = String::from(
+        "<?xml version=\"1.0\" encoding=\"UTF-8\" standalone=\"no\"?>\n<structure>\n\t<type>turing</type>\n\t<automaton>\n",
+    );
+
+    let mut ids = HashMap::new();
+    for (id, state) in septuple.states.iter().enumerate() {
+        ids.insert(state, id);
+        let (x, y) = (100 + 150 * (id % 5), 100 + 150 * (id / 5));
+        xml += &format!(
+            "\t\t<state id=\"{id}\" name=\"{}\">\n\t\t\t<x>{x}.0</x>\n\t\t\t<y>{y}.0</y>\n",
+            escape(state)
+        );
+        if *state == septuple.initial_state {
+            xml += "\t\t\t<initial/>\n";
+        }
+        if septuple.final_states.contains(state) {
+            xml += "\t\t\t<final/>\n";
+        }
+        xml += "\t\t</state>\n";
+    }
+
+    for ((from_state, read_symbol), transition) in septuple.concrete_transitions() {
+        let movement = Movement::symbol(transition.move_to, "S");
+        xml += &format!(
+            "\t\t<transition>\n\t\t\t<from>{}</from>\n\t\t\t<to>{}</to>\n\t\t\t{}\n\t\t\t{}\n\t\t\t<move>{movement}</move>\n\t\t</transition>\n",
+            ids[&from_state],
+            ids[&transition.next_state],
+            symbol_element("read", &read_symbol, &septuple.blank_symbol),
+            symbol_element("write", &transition.written(&read_symbol), &septuple.blank_symbol),
+        );
+    }
+
+    xml += "\t</automaton>\n</structure>\n";
+    xml
+}
+
+fn child<'a, 'input>(node: Node<'a, 'input>, name: &str) -> Option<Node<'a, 'input>> {
+    node.children().find(|child| child.has_tag_name(name))
+}
+
+fn children<'a, 'input: 'a>(
+    node: Node<'a, 'input>,
+    name: &'a str,
+) -> impl Iterator<Item = Node<'a, 'input>> + 'a {
+    node.children()
+        .filter(move |child| child.has_tag_name(name))
+}
+
+fn child_text<'a>(node: Node<'a, '_>, name: &str) -> Option<&'a str> {
+    child(node, name).map(|child| child.text().unwrap_or(""))
+}
+
+/// Lê o símbolo do elemento `name` de uma transição. Retorna [`None`] caso o elemento
+/// esteja vazio, o que representa o branco.
+fn symbol(transition: Node, name: &'static str) -> Result<Option<char>, JflapError> {
+    let text = child_text(transition, name).ok_or(JflapError::Missing(name))?;
+    let mut chars = text.chars();
+    match (chars.next(), chars.next()) {
+        (None, _) => Ok(None),
+        (Some(symbol), None) => Ok(Some(symbol)),
+        _ => Err(JflapError::InvalidSymbol(text.to_string())),
+    }
+}
+
+/// Escreve o elemento `name` com o símbolo `symbol`, vazio caso seja o branco.
+fn symbol_element(name: &str, symbol: &Symbol, blank: &Symbol) -> String {
+    if symbol == blank {
+        format!("<{name}/>")
+    } else {
+        format!("<{name}>{}</{name}>", escape(symbol.as_str()))
+    }
+}
+
+/// Escapa os caracteres especiais do XML.
+fn escape(text: &str) -> String {
+    text.replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('"', "&quot;")
+}
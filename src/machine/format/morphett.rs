@@ -0,0 +1,400 @@
+//! Conversão entre sétuplas e o formato do simulador de
+//! [Anthony Morphett](https://morphett.info/turing/turing.html), também usado por outros
+//! simuladores online. Cada linha define uma transição na forma `<estado> <lido>
+//! <escrito> <direção> <próximo estado>`, e `;` inicia um comentário até o fim da linha.
+//!
+//! No formato, `_` representa o branco e a direção é `l`, `r` ou `*` (parado). Um `*`
+//! como estado ou símbolo lido corresponde a qualquer estado ou símbolo, e como símbolo
+//! escrito ou próximo estado mantém o atual. A máquina inicia no estado `0`, para ao
+//! alcançar qualquer estado cujo nome comece com `halt`, e sua fita é infinita em ambas
+//! as direções. Os símbolos do formato possuem um único caractere; símbolos com nomes
+//! maiores são exportados como estão.
+
+use std::{collections::HashMap, fmt::Display};
+
+use indexmap::{IndexMap, IndexSet};
+
+use super::FormatWarning;
+use crate::machine::{
+    sep::{KeyedTransition, LoadError, Movement, Read, Septuple, SymbolClass, Transition},
+    symbol::Symbol,
+    Acceptance,
+};
+
+/// Representação do branco no formato.
+const BLANK: char = '_';
+/// Estado ou símbolo que corresponde a qualquer valor.
+const WILDCARD: &str = "*";
+/// Estado inicial das máquinas no formato.
+const INITIAL_STATE: &str = "0";
+/// Prefixo dos estados de parada.
+const HALT_PREFIX: &str = "halt";
+/// Estado de parada usado para aceitar a fita.
+const HALT_ACCEPT: &str = "halt-accept";
+/// Estado de parada usado para rejeitar a fita.
+const HALT_REJECT: &str = "halt-reject";
+
+/// Erro encontrado ao interpretar uma linha do formato.
+#[derive(Debug)]
+pub struct MorphettError {
+    /// Linha do erro, iniciada em 1.
+    pub line: usize,
+    pub kind: MorphettErrorKind,
+}
+
+/// Os tipos de erro que podem ocorrer no formato.
+#[derive(Debug)]
+pub enum MorphettErrorKind {
+    /// A linha não possui os cinco campos de uma transição.
+    FieldCount(usize),
+    /// O símbolo possui mais de um caractere.
+    InvalidSymbol(String),
+    /// A direção não é `l`, `r` ou `*`.
+    InvalidDirection(String),
+    /// A transição conflita com a transição declarada na linha indicada.
+    Conflict(usize),
+    /// O arquivo não possui transições.
+    Empty,
+}
+
+impl Display for MorphettError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "linha {}: {}", self.line, self.kind)
+    }
+}
+impl std::error::Error for MorphettError {}
+
+impl Display for MorphettErrorKind {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            MorphettErrorKind::FieldCount(count) => {
+                write!(f, "uma transição possui 5 campos, encontrados {count}")
+            }
+            MorphettErrorKind::InvalidSymbol(symbol) => {
+                write!(f, "símbolo \"{symbol}\" possui mais de um caractere")
+            }
+            MorphettErrorKind::InvalidDirection(direction) => {
+                write!(f, "direção \"{direction}\" inválida, use l, r ou *")
+            }
+            MorphettErrorKind::Conflict(line) => {
+                write!(f, "transição conflitante com a declarada na linha {line}")
+            }
+            MorphettErrorKind::Empty => write!(f, "nenhuma transição definida"),
+        }
+    }
+}
+
+impl From<MorphettError> for LoadError {
+    fn from(err: MorphettError) -> Self {
+        LoadError::Morphett(err)
+    }
+}
+
+/// Valor de um campo que aceita `*`. [`None`] representa `*`.
+type Pattern<T> = Option<T>;
+
+/// As ações de uma linha, em que [`None`] mantém o símbolo ou o estado atual.
+#[derive(Clone, PartialEq, Eq)]
+struct Action {
+    write_symbol: Option<char>,
+    move_to: Option<Movement>,
+    next_state: Option<String>,
+}
+
+/// Uma linha do arquivo.
+struct Line {
+    number: usize,
+    from_state: Pattern<String>,
+    read_symbol: Pattern<char>,
+    action: Action,
+}
+
+/// Cria uma sétupla à partir do formato de Morphett.
+///
+/// Os estados `halt-accept` e `halt-reject` se tornam estados de aceitação e de
+/// rejeição, e os demais estados iniciados por `halt`, estados de parada. O alfabeto
+/// contém todos os símbolos usados, e todos exceto o branco são símbolos de entrada.
+/// Transições com `*` são expandidas seguindo a precedência do formato: estado e símbolo
+/// exatos, estado exato e qualquer símbolo, qualquer estado e símbolo exato e, por fim,
+/// qualquer estado e símbolo.
+///
+/// Caso o estado `0` não exista, a máquina inicia no estado da primeira linha, e um
+/// [`FormatWarning::InitialState`] é retornado junto dos avisos de transições idênticas.
+///
+/// # Erros
+/// Retorna um [`MorphettError`] com a linha do primeiro erro encontrado.
+pub fn from_morphett(text: &str) -> Result<(Septuple, Vec<FormatWarning>), LoadError> {
+    let mut lines = vec![];
+    for (index, line) in text.lines().enumerate() {
+        let number = index + 1;
+        let content = line.split(';').next().unwrap_or("");
+        let fields: Vec<_> = content.split_whitespace().collect();
+        let error = |kind| MorphettError { line: number, kind };
+        let fields = match fields[..] {
+            [] => continue,
+            // um `!` ao final marca um ponto de parada no simulador original
+            [from, read, write, direction, next] | [from, read, write, direction, next, "!"] => {
+                [from, read, write, direction, next]
+            }
+            _ => return Err(error(MorphettErrorKind::FieldCount(fields.len())).into()),
+        };
+        lines.push(parse_line(number, fields).map_err(error)?);
+    }
+    let first = lines.first().ok_or(MorphettError {
+        line: 1,
+        kind: MorphettErrorKind::Empty,
+    })?;
+
+    let mut states = IndexSet::new();
+    let mut alphabet = IndexSet::new();
+    for line in &lines {
+        states.extend(line.from_state.clone());
+        states.extend(line.action.next_state.clone());
+        alphabet.extend(line.read_symbol);
+        alphabet.extend(line.action.write_symbol);
+    }
+    let mut warnings = vec![];
+    let initial_state = if states.contains(INITIAL_STATE) {
+        INITIAL_STATE.to_string()
+    } else {
+        // um estado é sempre mencionado, mesmo que apenas como próximo estado
+        let state = first
+            .from_state
+            .clone()
+            .or_else(|| first.action.next_state.clone())
+            .unwrap_or_else(|| INITIAL_STATE.to_string());
+        warnings.push(FormatWarning::InitialState {
+            expected: INITIAL_STATE,
+            used: state.clone(),
+        });
+        state
+    };
+    states.shift_insert(0, initial_state.clone());
+    alphabet.insert(BLANK);
+
+    let halting = |state: &&String| state.starts_with(HALT_PREFIX);
+    let final_states: IndexSet<_> = states
+        .iter()
+        .filter(|state| *state == HALT_ACCEPT)
+        .cloned()
+        .collect();
+    let reject_states: IndexSet<_> = states
+        .iter()
+        .filter(|state| *state == HALT_REJECT)
+        .cloned()
+        .collect();
+    let halt_states: IndexSet<_> = states
+        .iter()
+        .filter(halting)
+        .filter(|state| !final_states.contains(*state) && !reject_states.contains(*state))
+        .cloned()
+        .collect();
+
+    let running: Vec<_> = states.iter().filter(|state| !halting(state)).collect();
+    let transitions = expand(&lines, &running, &alphabet)?;
+    let alphabet: IndexSet<Symbol> = alphabet.into_iter().map(Symbol::from).collect();
+    let input_symbols = alphabet.iter().filter(|&s| *s != BLANK).cloned().collect();
+    let septuple = Septuple {
+        final_states,
+        reject_states,
+        halt_states,
+        two_way_tape: true,
+        ..Septuple::new(alphabet, BLANK.into(), input_symbols, states, initial_state)
+    };
+    let (septuple, duplicates) = septuple.with_transitions(transitions)?;
+    warnings.extend(duplicates.into_iter().map(FormatWarning::Duplicate));
+    Ok((septuple, warnings))
+}
+
+/// Converte a sétupla para o formato de Morphett, na ordem de declaração das transições.
+///
+/// Como o formato fixa os nomes do estado inicial e dos estados de parada, o estado
+/// inicial é renomeado para `0`, os estados de aceitação para `halt-accept`, os de
+/// rejeição para `halt-reject` e os de parada para `halt`. Os demais estados mantêm o
+/// nome, a não ser que conflitem com esses nomes. O branco é escrito como `_`.
+///
+/// Transições que leem qualquer símbolo são escritas com `*`, que tem a mesma
+/// precedência no simulador; as demais classes de símbolos são expandidas. Caso o estado
+/// inicial também seja de parada, uma única transição leva do estado `0` ao estado de
+/// parada correspondente, sem alterar a fita.
+pub fn to_morphett(septuple: &Septuple) -> String {
+    let names = rename_states(septuple);
+    let symbol = |symbol: &Symbol| {
+        if *symbol == septuple.blank_symbol {
+            BLANK.to_string()
+        } else {
+            symbol.to_string()
+        }
+    };
+
+    let mut text = String::new();
+    if septuple.initial_state != INITIAL_STATE {
+        text += &format!("; estado inicial original: {}\n", septuple.initial_state);
+    }
+    if let Some(halt) = halting_name(septuple, &septuple.initial_state) {
+        text += &format!("{INITIAL_STATE} {WILDCARD} {WILDCARD} {WILDCARD} {halt}\n");
+    }
+    for (from_state, read_symbol, transition) in septuple.declared_transitions() {
+        if septuple.halting_acceptance(from_state).is_some() {
+            continue;
+        }
+        // `any` tem a mesma precedência que `*` no simulador; as demais classes são
+        // expandidas em um símbolo por linha
+        let read_symbols = match read_symbol {
+            Read::Symbol(read_symbol) => vec![symbol(&read_symbol)],
+            Read::Class(SymbolClass::Any) => vec![WILDCARD.to_string()],
+            Read::Class(_) => septuple
+                .handled_symbols(from_state, transition)
+                .iter()
+                .map(symbol)
+                .collect(),
+        };
+        let direction = match transition.move_to {
+            Some(Movement::R) => "r",
+            Some(Movement::L) => "l",
+            None => WILDCARD,
+        };
+        let write_symbol = transition
+            .write_symbol
+            .as_ref()
+            .map_or(WILDCARD.to_string(), symbol);
+        for read_symbol in read_symbols {
+            text += &format!(
+                "{} {read_symbol} {write_symbol} {direction} {}\n",
+                names[from_state], names[&transition.next_state],
+            );
+        }
+    }
+    text
+}
+
+fn parse_line(number: usize, fields: [&str; 5]) -> Result<Line, MorphettErrorKind> {
+    let [from, read, write, direction, next] = fields;
+    let state = |state: &str| (state != WILDCARD).then(|| state.to_string());
+
+    let move_to = match direction {
+        "r" | "R" => Some(Movement::R),
+        "l" | "L" => Some(Movement::L),
+        WILDCARD => None,
+        _ => return Err(MorphettErrorKind::InvalidDirection(direction.to_string())),
+    };
+    Ok(Line {
+        number,
+        from_state: state(from),
+        read_symbol: parse_symbol(read)?,
+        action: Action {
+            write_symbol: parse_symbol(write)?,
+            move_to,
+            next_state: state(next),
+        },
+    })
+}
+
+/// Interpreta um símbolo, retornando [`None`] caso seja `*`.
+fn parse_symbol(symbol: &str) -> Result<Pattern<char>, MorphettErrorKind> {
+    if symbol == WILDCARD {
+        return Ok(None);
+    }
+    let mut chars = symbol.chars();
+    match (chars.next(), chars.next()) {
+        (Some(symbol), None) => Ok(Some(symbol)),
+        _ => Err(MorphettErrorKind::InvalidSymbol(symbol.to_string())),
+    }
+}
+
+/// Expande as linhas em transições para os estados `states` e os símbolos `alphabet`.
+/// Cada par de estado e símbolo usa a linha de maior precedência que o corresponde, e as
+/// transições seguem a ordem das linhas.
+///
+/// # Erros
+/// Retorna um erro caso duas linhas com o mesmo estado e símbolo definam ações
+/// diferentes.
+fn expand(
+    lines: &[Line],
+    states: &[&String],
+    alphabet: &IndexSet<char>,
+) -> Result<Vec<KeyedTransition>, MorphettError> {
+    // primeira linha de cada padrão de estado e símbolo
+    let mut patterns: HashMap<(&Pattern<String>, &Pattern<char>), &Line> = HashMap::new();
+    for line in lines {
+        let first = patterns
+            .entry((&line.from_state, &line.read_symbol))
+            .or_insert(line);
+        if first.action != line.action {
+            return Err(MorphettError {
+                line: line.number,
+                kind: MorphettErrorKind::Conflict(first.number),
+            });
+        }
+    }
+
+    let mut chosen = IndexMap::new();
+    for &state in states {
+        for &symbol in alphabet {
+            let (state_pattern, symbol_pattern) = (Some(state.clone()), Some(symbol));
+            let line = [
+                (&state_pattern, &symbol_pattern),
+                (&state_pattern, &None),
+                (&None, &symbol_pattern),
+                (&None, &None),
+            ]
+            .into_iter()
+            .find_map(|pattern| patterns.get(&pattern));
+            if let Some(line) = line {
+                chosen.insert((state.clone(), symbol), line.number);
+            }
+        }
+    }
+
+    let lines: HashMap<_, _> = lines.iter().map(|line| (line.number, line)).collect();
+    let mut transitions: Vec<_> = chosen.into_iter().collect();
+    transitions.sort_by_key(|(_, number)| *number);
+    Ok(transitions
+        .into_iter()
+        .map(|((state, symbol), number)| {
+            let action = &lines[&number].action;
+            let transition = Transition {
+                write_symbol: Some(action.write_symbol.unwrap_or(symbol).into()),
+                next_state: action.next_state.clone().unwrap_or_else(|| state.clone()),
+                move_to: action.move_to,
+            };
+            ((state, symbol.into()), transition)
+        })
+        .collect())
+}
+
+/// Retorna o nome, no formato, do estado de parada correspondente a `state`, caso ele
+/// seja um estado final, de rejeição ou de parada.
+fn halting_name(septuple: &Septuple, state: &String) -> Option<&'static str> {
+    septuple
+        .halting_acceptance(state)
+        .map(|acceptance| match acceptance {
+            Acceptance::Accepted => HALT_ACCEPT,
+            Acceptance::Rejected(_) => HALT_REJECT,
+            _ => HALT_PREFIX,
+        })
+}
+
+/// Define o nome de cada estado no formato, conforme [`to_morphett`].
+fn rename_states(septuple: &Septuple) -> HashMap<&String, String> {
+    let reserved = |name: &str| name == INITIAL_STATE || name.starts_with(HALT_PREFIX);
+    let mut used: IndexSet<String> = IndexSet::new();
+    let mut names = HashMap::new();
+
+    for state in &septuple.states {
+        let name = if *state == septuple.initial_state {
+            INITIAL_STATE.to_string()
+        } else if let Some(halt) = halting_name(septuple, state) {
+            halt.to_string()
+        } else {
+            let mut name = state.clone();
+            while reserved(&name) || used.contains(&name) {
+                name.insert(0, '_');
+            }
+            name
+        };
+        used.insert(name.clone());
+        names.insert(state, name);
+    }
+    names
+}
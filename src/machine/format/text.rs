@@ -0,0 +1,578 @@
+//! Formato textual compacto de sétuplas (`.tm`), com uma transição por linha:
+//!
+//! ```text
+//! // reconhece 0^n 1^n
+//! blank: B
+//! initial: q0
+//! final: q3
+//! input: 0 1
+//!
+//! q0 0 -> q1 X R
+//! q1 * -> q1 * R
+//! ```
+//!
+//! Cada transição segue a forma `<estado> <lido> -> <próximo estado> <escrito>
+//! <movimento>`, sendo o movimento `L`, `R` ou `S` (parado). Linhas na forma `<diretiva>:
+//! <valores>` definem os demais campos da sétupla, e `//` inicia um comentário até o fim
+//! da linha.
+//!
+//! O símbolo lido pode ser uma classe de símbolos: `*` (qualquer símbolo), `{a,b}`
+//! (apenas `a` e `b`) ou `!{a,b}` (qualquer símbolo exceto `a` e `b`). Transições exatas
+//! têm precedência sobre classes, como descrito em [`Septuple::find_transition`]. Um `*`
+//! como símbolo escrito mantém o símbolo lido.
+//!
+//! Símbolos podem ter mais de um caractere, como `X1` ou `blank`, desde que não contenham
+//! espaços em branco nem vírgulas e não comecem com `{` ou `!{`.
+
+use std::{collections::HashMap, fmt::Display};
+
+use indexmap::IndexSet;
+
+use crate::machine::{
+    sep::{
+        DeclaredTransition, LoadError, Movement, Read, Septuple, SymbolClass, Transition,
+        TransitionClash,
+    },
+    symbol::Symbol,
+};
+
+/// Símbolo usado nas transições para representar qualquer símbolo lido, ou o próprio
+/// símbolo lido quando escrito.
+const WILDCARD: &str = "*";
+/// Símbolo branco usado caso a diretiva `blank` não seja declarada.
+const DEFAULT_BLANK: &str = "B";
+
+/// Erro encontrado ao interpretar uma sétupla no formato textual.
+#[derive(Debug)]
+pub struct TextError {
+    /// Linha do erro, iniciada em 1.
+    pub line: usize,
+    /// Coluna do erro, em caracteres, iniciada em 1.
+    pub column: usize,
+    pub kind: TextErrorKind,
+}
+
+/// Os tipos de erro que podem ocorrer no formato textual.
+#[derive(Debug)]
+pub enum TextErrorKind {
+    /// A diretiva não é conhecida.
+    UnknownDirective(String),
+    /// A diretiva foi declarada mais de uma vez.
+    DuplicateDirective(String),
+    /// A diretiva recebeu um número inválido de valores.
+    DirectiveArity(String),
+    /// O valor da diretiva `tape` não é `one-way` nem `two-way`.
+    InvalidTape(String),
+    /// A linha terminou antes do esperado; contém a descrição do que era esperado.
+    Expected(&'static str),
+    /// Foi encontrado um elemento diferente do esperado.
+    Unexpected {
+        expected: &'static str,
+        found: String,
+    },
+    /// O movimento não é `L`, `R` ou `S`.
+    InvalidMove(String),
+    /// A classe de símbolos não segue a forma `{a,b}` ou `!{a,b}`.
+    InvalidClass(String),
+    /// A transição conflita com a transição declarada na linha indicada.
+    Conflict(usize),
+    /// O arquivo não define um estado inicial nem transições.
+    NoInitialState,
+}
+
+impl Display for TextError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "linha {}, coluna {}: {}",
+            self.line, self.column, self.kind
+        )
+    }
+}
+impl std::error::Error for TextError {}
+
+impl Display for TextErrorKind {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            TextErrorKind::UnknownDirective(name) => write!(f, "diretiva \"{name}\" desconhecida"),
+            TextErrorKind::DuplicateDirective(name) => {
+                write!(f, "diretiva \"{name}\" declarada mais de uma vez")
+            }
+            TextErrorKind::DirectiveArity(name) => {
+                write!(f, "diretiva \"{name}\" aceita exatamente um valor")
+            }
+            TextErrorKind::InvalidTape(tape) => {
+                write!(
+                    f,
+                    "fita \"{tape}\" inválida, use \"one-way\" ou \"two-way\""
+                )
+            }
+            TextErrorKind::Expected(expected) => write!(f, "{expected} esperado"),
+            TextErrorKind::Unexpected { expected, found } => {
+                write!(f, "{expected} esperado, encontrado \"{found}\"")
+            }
+            TextErrorKind::InvalidMove(movement) => {
+                write!(f, "movimento \"{movement}\" inválido, use L, R ou S")
+            }
+            TextErrorKind::InvalidClass(class) => {
+                write!(
+                    f,
+                    "classe de símbolos \"{class}\" inválida, use {{a,b}} ou !{{a,b}}"
+                )
+            }
+            TextErrorKind::Conflict(line) => {
+                write!(f, "transição conflitante com a declarada na linha {line}")
+            }
+            TextErrorKind::NoInitialState => write!(f, "nenhum estado inicial definido"),
+        }
+    }
+}
+
+impl From<TextError> for LoadError {
+    fn from(err: TextError) -> Self {
+        LoadError::Text(err)
+    }
+}
+
+/// Uma palavra da linha, com sua coluna (iniciada em 1).
+type Token<'t> = (usize, &'t str);
+
+/// Uma transição como escrita no arquivo. O símbolo escrito [`None`] representa `*`.
+struct RawTransition {
+    line: usize,
+    from_state: String,
+    read_symbol: Read,
+    write_symbol: Option<Symbol>,
+    next_state: String,
+    move_to: Option<Movement>,
+}
+
+/// Valores das diretivas declaradas no arquivo.
+#[derive(Default)]
+struct Directives {
+    alphabet: Option<IndexSet<Symbol>>,
+    input: Option<IndexSet<Symbol>>,
+    blank: Option<Symbol>,
+    states: Option<IndexSet<String>>,
+    initial: Option<String>,
+    finals: Option<IndexSet<String>>,
+    rejects: Option<IndexSet<String>>,
+    halts: Option<IndexSet<String>>,
+    two_way: Option<bool>,
+}
+
+/// Cria uma sétupla à partir do formato textual, retornando também as transições
+/// declaradas mais de uma vez de forma idêntica.
+///
+/// Diretivas ausentes são inferidas: o branco é `B`, o estado inicial é o primeiro
+/// estado das transições, o alfabeto são todos os símbolos usados, os símbolos de
+/// entrada são todos os símbolos do alfabeto exceto o branco e os estados são todos os
+/// estados mencionados, na ordem em que aparecem.
+///
+/// # Erros
+/// Retorna um [`TextError`] com a linha e a coluna do primeiro erro encontrado.
+pub fn from_text(text: &str) -> Result<(Septuple, Vec<TransitionClash>), LoadError> {
+    let mut directives = Directives::default();
+    let mut transitions = vec![];
+
+    for (index, line) in text.lines().enumerate() {
+        let line_number = index + 1;
+        let content = match line.find("//") {
+            Some(comment) => &line[..comment],
+            None => line,
+        };
+        let tokens = tokenize(content);
+        let Some(&(column, first)) = tokens.first() else {
+            continue;
+        };
+        let at = |column, kind| TextError {
+            line: line_number,
+            column,
+            kind,
+        };
+
+        match first.strip_suffix(':') {
+            Some(name) => directives
+                .set((column, name), &tokens[1..])
+                .map_err(|(column, kind)| at(column.unwrap_or(column_after(&tokens)), kind))?,
+            None => transitions.push(
+                parse_transition(line_number, &tokens)
+                    .map_err(|(column, kind)| at(column.unwrap_or(column_after(&tokens)), kind))?,
+            ),
+        }
+    }
+
+    build(directives, transitions)
+}
+
+/// Converte a sétupla para o formato textual. Todas as diretivas são escritas, para que
+/// a ordem dos conjuntos seja preservada, e as transições seguem a ordem do arquivo de
+/// origem.
+pub fn to_text(septuple: &Septuple) -> String {
+    let symbols = |set: &IndexSet<Symbol>| set.iter().map(Symbol::to_string).collect::<Vec<_>>();
+    let states = |set: &IndexSet<String>| set.iter().cloned().collect::<Vec<_>>();
+
+    let mut lines = vec![
+        directive("alphabet", symbols(&septuple.alphabet)),
+        directive("input", symbols(&septuple.input_symbols)),
+        directive("blank", vec![septuple.blank_symbol.to_string()]),
+        directive("states", states(&septuple.states)),
+        directive("initial", vec![septuple.initial_state.clone()]),
+        directive("final", states(&septuple.final_states)),
+    ];
+    if !septuple.reject_states.is_empty() {
+        lines.push(directive("reject", states(&septuple.reject_states)));
+    }
+    if !septuple.halt_states.is_empty() {
+        lines.push(directive("halt", states(&septuple.halt_states)));
+    }
+    if septuple.two_way_tape {
+        lines.push(directive("tape", vec!["two-way".to_string()]));
+    }
+    lines.push(String::new());
+
+    for (from_state, read_symbol, transition) in septuple.declared_transitions() {
+        let movement = Movement::symbol(transition.move_to, "S");
+        let write_symbol = match &transition.write_symbol {
+            Some(symbol) => symbol.to_string(),
+            None => WILDCARD.to_string(),
+        };
+        lines.push(format!(
+            "{from_state} {} -> {} {write_symbol} {movement}",
+            read_pattern(&read_symbol),
+            transition.next_state
+        ));
+    }
+
+    let mut text = lines.join("\n");
+    text.push('\n');
+    text
+}
+
+/// Escreve o símbolo lido por uma transição na sintaxe do formato.
+fn read_pattern(read: &Read) -> String {
+    let list = |symbols: &IndexSet<Symbol>| {
+        let symbols: Vec<_> = symbols.iter().map(Symbol::as_str).collect();
+        symbols.join(",")
+    };
+    match read {
+        Read::Symbol(symbol) => symbol.to_string(),
+        Read::Class(SymbolClass::Any) => WILDCARD.to_string(),
+        Read::Class(SymbolClass::In(symbols)) => format!("{{{}}}", list(symbols)),
+        Read::Class(SymbolClass::Except(symbols)) => format!("!{{{}}}", list(symbols)),
+    }
+}
+
+fn directive(name: &str, values: Vec<String>) -> String {
+    if values.is_empty() {
+        format!("{name}:")
+    } else {
+        format!("{name}: {}", values.join(" "))
+    }
+}
+
+/// Separa a linha em palavras delimitadas por espaços em branco.
+fn tokenize(line: &str) -> Vec<Token<'_>> {
+    let mut tokens = vec![];
+    let mut start = None;
+    for (column, (byte, c)) in line.char_indices().enumerate() {
+        match (start, c.is_whitespace()) {
+            (None, false) => start = Some((column + 1, byte)),
+            (Some((token_column, token_byte)), true) => {
+                tokens.push((token_column, &line[token_byte..byte]));
+                start = None;
+            }
+            _ => (),
+        }
+    }
+    if let Some((column, byte)) = start {
+        tokens.push((column, &line[byte..]));
+    }
+    tokens
+}
+
+/// Retorna a coluna logo após a última palavra da linha, usada em erros de fim de linha.
+fn column_after(tokens: &[Token]) -> usize {
+    tokens
+        .last()
+        .map_or(1, |(column, token)| column + token.chars().count())
+}
+
+/// Um erro ainda sem linha; a coluna é [`None`] caso o erro esteja no fim da linha.
+type LineError = (Option<usize>, TextErrorKind);
+
+fn parse_symbol((_, token): Token) -> Symbol {
+    token.into()
+}
+
+/// Interpreta um símbolo escrito, retornando [`None`] caso seja `*`.
+fn parse_write(token: Token) -> Option<Symbol> {
+    if token.1 == WILDCARD {
+        None
+    } else {
+        Some(parse_symbol(token))
+    }
+}
+
+/// Interpreta um símbolo lido, que pode ser um símbolo ou uma classe de símbolos.
+fn parse_read((column, token): Token) -> Result<Read, LineError> {
+    if token == WILDCARD {
+        return Ok(Read::Class(SymbolClass::Any));
+    }
+    let (except, list) = match token.strip_prefix('!') {
+        Some(list) => (true, list),
+        None => (false, token),
+    };
+    let Some(list) = list.strip_prefix('{') else {
+        return Ok(Read::Symbol(parse_symbol((column, token))));
+    };
+    let invalid = || (Some(column), TextErrorKind::InvalidClass(token.to_string()));
+    let list = list.strip_suffix('}').ok_or_else(invalid)?;
+    let symbols = list
+        .split(',')
+        .map(|symbol| match symbol {
+            "" => Err(invalid()),
+            _ => Ok(Symbol::from(symbol)),
+        })
+        .collect::<Result<IndexSet<Symbol>, LineError>>()?;
+    Ok(Read::Class(if except {
+        SymbolClass::Except(symbols)
+    } else {
+        SymbolClass::In(symbols)
+    }))
+}
+
+fn parse_transition(line: usize, tokens: &[Token]) -> Result<RawTransition, LineError> {
+    let mut tokens = tokens.iter().copied();
+    let mut next = |expected| {
+        tokens
+            .next()
+            .ok_or((None, TextErrorKind::Expected(expected)))
+    };
+
+    let from_state = next("estado")?.1.to_string();
+    let read_symbol = parse_read(next("símbolo lido")?)?;
+    let arrow = next("\"->\"")?;
+    if arrow.1 != "->" {
+        return Err((
+            Some(arrow.0),
+            TextErrorKind::Unexpected {
+                expected: "\"->\"",
+                found: arrow.1.to_string(),
+            },
+        ));
+    }
+    let next_state = next("próximo estado")?.1.to_string();
+    let write_symbol = parse_write(next("símbolo escrito")?);
+    let movement = next("movimento")?;
+    let move_to = match movement.1 {
+        "R" => Some(Movement::R),
+        "L" => Some(Movement::L),
+        "S" => None,
+        _ => {
+            return Err((
+                Some(movement.0),
+                TextErrorKind::InvalidMove(movement.1.to_string()),
+            ))
+        }
+    };
+    if let Some((column, token)) = tokens.next() {
+        return Err((
+            Some(column),
+            TextErrorKind::Unexpected {
+                expected: "fim da linha",
+                found: token.to_string(),
+            },
+        ));
+    }
+
+    Ok(RawTransition {
+        line,
+        from_state,
+        read_symbol,
+        write_symbol,
+        next_state,
+        move_to,
+    })
+}
+
+impl Directives {
+    /// Define o valor da diretiva `name`, iniciada na coluna `column`, à partir das
+    /// palavras `values`.
+    fn set(&mut self, (column, name): Token, values: &[Token]) -> Result<(), LineError> {
+        let duplicate = || {
+            (
+                Some(column),
+                TextErrorKind::DuplicateDirective(name.to_string()),
+            )
+        };
+        let single = || match values {
+            [value] => Ok(*value),
+            [] => Err((None, TextErrorKind::DirectiveArity(name.to_string()))),
+            [_, (column, _), ..] => Err((
+                Some(*column),
+                TextErrorKind::DirectiveArity(name.to_string()),
+            )),
+        };
+        let symbols =
+            || -> IndexSet<Symbol> { values.iter().map(|&token| parse_symbol(token)).collect() };
+        let states = || values.iter().map(|(_, state)| state.to_string()).collect();
+
+        match name {
+            "alphabet" if self.alphabet.is_some() => return Err(duplicate()),
+            "alphabet" => self.alphabet = Some(symbols()),
+            "input" if self.input.is_some() => return Err(duplicate()),
+            "input" => self.input = Some(symbols()),
+            "blank" if self.blank.is_some() => return Err(duplicate()),
+            "blank" => self.blank = Some(parse_symbol(single()?)),
+            "states" if self.states.is_some() => return Err(duplicate()),
+            "states" => self.states = Some(states()),
+            "initial" if self.initial.is_some() => return Err(duplicate()),
+            "initial" => self.initial = Some(single()?.1.to_string()),
+            "final" if self.finals.is_some() => return Err(duplicate()),
+            "final" => self.finals = Some(states()),
+            "reject" if self.rejects.is_some() => return Err(duplicate()),
+            "reject" => self.rejects = Some(states()),
+            "halt" if self.halts.is_some() => return Err(duplicate()),
+            "halt" => self.halts = Some(states()),
+            "tape" if self.two_way.is_some() => return Err(duplicate()),
+            "tape" => {
+                let (tape_column, tape) = single()?;
+                self.two_way = match tape {
+                    "one-way" => Some(false),
+                    "two-way" => Some(true),
+                    _ => {
+                        return Err((
+                            Some(tape_column),
+                            TextErrorKind::InvalidTape(tape.to_string()),
+                        ))
+                    }
+                }
+            }
+            _ => {
+                return Err((
+                    Some(column),
+                    TextErrorKind::UnknownDirective(name.to_string()),
+                ))
+            }
+        }
+        Ok(())
+    }
+}
+
+/// Constrói a sétupla à partir das diretivas e transições lidas, inferindo as diretivas
+/// ausentes.
+fn build(
+    directives: Directives,
+    raw_transitions: Vec<RawTransition>,
+) -> Result<(Septuple, Vec<TransitionClash>), LoadError> {
+    let blank = directives.blank.unwrap_or_else(|| DEFAULT_BLANK.into());
+    let initial_state = match (directives.initial, raw_transitions.first()) {
+        (Some(initial), _) => initial,
+        (None, Some(first)) => first.from_state.clone(),
+        (None, None) => {
+            return Err(TextError {
+                line: 1,
+                column: 1,
+                kind: TextErrorKind::NoInitialState,
+            }
+            .into())
+        }
+    };
+    let final_states = directives.finals.unwrap_or_default();
+    let reject_states = directives.rejects.unwrap_or_default();
+    let halt_states = directives.halts.unwrap_or_default();
+
+    let alphabet = directives.alphabet.unwrap_or_else(|| {
+        let mut alphabet: IndexSet<Symbol> = directives.input.iter().flatten().cloned().collect();
+        for transition in &raw_transitions {
+            match &transition.read_symbol {
+                Read::Symbol(symbol) => alphabet.extend([symbol.clone()]),
+                Read::Class(class) => alphabet.extend(class.symbols().cloned()),
+            }
+            alphabet.extend(transition.write_symbol.clone());
+        }
+        alphabet.insert(blank.clone());
+        alphabet
+    });
+    let input_symbols = directives.input.unwrap_or_else(|| {
+        alphabet
+            .iter()
+            .filter(|&symbol| *symbol != blank)
+            .cloned()
+            .collect()
+    });
+    let states = directives.states.unwrap_or_else(|| {
+        let mut states = IndexSet::from([initial_state.clone()]);
+        for transition in &raw_transitions {
+            states.insert(transition.from_state.clone());
+            states.insert(transition.next_state.clone());
+        }
+        states.extend(final_states.iter().cloned());
+        states.extend(reject_states.iter().cloned());
+        states.extend(halt_states.iter().cloned());
+        states
+    });
+
+    let transition_lines = raw_transitions.iter().map(|raw| raw.line).collect();
+    let transitions = declarations(raw_transitions)?;
+    let septuple = Septuple {
+        final_states,
+        reject_states,
+        halt_states,
+        two_way_tape: directives.two_way.unwrap_or(false),
+        transition_lines,
+        ..Septuple::new(alphabet, blank, input_symbols, states, initial_state)
+    };
+    septuple.with_declarations(transitions)
+}
+
+/// Converte as transições lidas em transições declaradas, na ordem do arquivo.
+///
+/// # Erros
+/// Retorna um erro caso duas transições partam do mesmo estado e símbolo, ou do mesmo
+/// estado e classe, mas definam ações diferentes.
+fn declarations(raw_transitions: Vec<RawTransition>) -> Result<Vec<DeclaredTransition>, TextError> {
+    // linha e ação da primeira declaração de cada chave
+    let mut exact = HashMap::new();
+    let mut classes: Vec<(&String, &SymbolClass, usize, _)> = vec![];
+    for raw in &raw_transitions {
+        let action = (&raw.write_symbol, &raw.next_state, raw.move_to);
+        let (line, existing) = match &raw.read_symbol {
+            Read::Symbol(symbol) => *exact
+                .entry((&raw.from_state, symbol))
+                .or_insert((raw.line, action)),
+            Read::Class(class) => {
+                let found = classes.iter().find(|(state, existing, _, _)| {
+                    **state == raw.from_state && *existing == class
+                });
+                match found {
+                    Some(&(_, _, line, existing)) => (line, existing),
+                    None => {
+                        classes.push((&raw.from_state, class, raw.line, action));
+                        (raw.line, action)
+                    }
+                }
+            }
+        };
+        if existing != action {
+            return Err(TextError {
+                line: raw.line,
+                column: 1,
+                kind: TextErrorKind::Conflict(line),
+            });
+        }
+    }
+
+    Ok(raw_transitions
+        .into_iter()
+        .map(|raw| {
+            let transition = Transition {
+                write_symbol: raw.write_symbol,
+                next_state: raw.next_state,
+                move_to: raw.move_to,
+            };
+            ((raw.from_state, raw.read_symbol), transition)
+        })
+        .collect())
+}
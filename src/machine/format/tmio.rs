@@ -0,0 +1,343 @@
+//! Conversão entre sétuplas e o formato YAML do [turingmachine.io](https://turingmachine.io):
+//!
+//! ```yaml
+//! blank: ' '
+//! start state: right
+//! table:
+//!   right:
+//!     [0, 1]: R
+//!     ' ': {L: carry}
+//!   carry:
+//!     1: {write: 0, L}
+//!     [0, ' ']: {write: 1, L: done}
+//!   done:
+//! ```
+//!
+//! Cada estado da tabela mapeia símbolos lidos, ou listas de símbolos, para instruções.
+//! Uma instrução é uma direção (`L` ou `R`), que mantém o símbolo e o estado, ou um mapa
+//! com a direção, opcionalmente associada ao próximo estado, e o símbolo escrito em
+//! `write`. A máquina para ao não encontrar uma transição, e sua fita é infinita em ambas
+//! as direções.
+
+use std::{
+    collections::HashSet,
+    fmt::{Display, Write},
+};
+
+use indexmap::{IndexMap, IndexSet};
+use serde_yaml::{Mapping, Value};
+
+use crate::machine::{
+    sep::{LoadError, Movement, Read, Septuple, Transition, TransitionClash},
+    symbol::Symbol,
+};
+
+/// Define os erros que podem ocorrer ao importar uma máquina do turingmachine.io.
+#[derive(Debug)]
+pub enum TmioError {
+    /// O arquivo não é um YAML válido.
+    Yaml(serde_yaml::Error),
+    /// Um campo obrigatório não foi encontrado.
+    Missing(&'static str),
+    /// Um símbolo não é um único caractere.
+    InvalidSymbol(String),
+    /// Um estado não é um texto ou número.
+    InvalidState(String),
+    /// A instrução do estado e símbolo indicados é inválida.
+    InvalidInstruction { state: String, symbol: Symbol },
+    /// O símbolo é lido mais de uma vez no mesmo estado.
+    Conflict { state: String, symbol: Symbol },
+}
+
+impl Display for TmioError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            TmioError::Yaml(err) => write!(f, "{err}"),
+            TmioError::Missing(field) => write!(f, "campo \"{field}\" não encontrado"),
+            TmioError::InvalidSymbol(symbol) => {
+                write!(f, "símbolo {symbol} não é um único caractere")
+            }
+            TmioError::InvalidState(state) => write!(f, "estado {state} inválido"),
+            TmioError::InvalidInstruction { state, symbol } => {
+                write!(f, "instrução inválida para ({state}, {symbol})")
+            }
+            TmioError::Conflict { state, symbol } => {
+                write!(f, "símbolo {symbol} lido mais de uma vez no estado {state}")
+            }
+        }
+    }
+}
+impl std::error::Error for TmioError {}
+
+impl From<TmioError> for LoadError {
+    fn from(err: TmioError) -> Self {
+        LoadError::Tmio(err)
+    }
+}
+
+/// Cria uma sétupla à partir do formato do turingmachine.io.
+///
+/// Como o formato não define estados de aceitação, os estados sem transições se tornam
+/// estados de parada. O alfabeto contém o branco e todos os símbolos usados, e todos
+/// exceto o branco são símbolos de entrada. Os campos `input` e `name` são ignorados.
+///
+/// # Erros
+/// Retorna um erro caso o arquivo não siga o formato ou caso um símbolo seja lido mais
+/// de uma vez no mesmo estado.
+pub fn from_tmio(yaml: &str) -> Result<(Septuple, Vec<TransitionClash>), LoadError> {
+    let document: Mapping = serde_yaml::from_str(yaml).map_err(TmioError::Yaml)?;
+    let field = |name| document.get(name).ok_or(TmioError::Missing(name));
+
+    let blank = symbol(field("blank")?)?;
+    let initial_state = state(field("start state")?)?;
+    let table = match field("table")? {
+        Value::Mapping(table) => table,
+        _ => return Err(TmioError::Missing("table").into()),
+    };
+
+    let mut states = IndexSet::from([initial_state.clone()]);
+    let mut alphabet = IndexSet::new();
+    let mut transitions = vec![];
+    // chaves das transições já lidas, para encontrar conflitos
+    let mut keys = HashSet::new();
+    for (from_state, instructions) in table {
+        let from_state = state(from_state)?;
+        states.insert(from_state.clone());
+        let instructions = match instructions {
+            Value::Mapping(instructions) => instructions.clone(),
+            // estados sem instruções podem ser declarados com um valor vazio
+            Value::Null => Mapping::new(),
+            other => return Err(TmioError::InvalidState(describe(other)).into()),
+        };
+
+        for (symbols, instruction) in instructions {
+            let symbols = match symbols {
+                Value::Sequence(symbols) => symbols.iter().map(symbol).collect(),
+                other => symbol(&other).map(|symbol| vec![symbol]),
+            }?;
+            for read_symbol in symbols {
+                let transition = parse_instruction(&from_state, &read_symbol, &instruction)?;
+                let key = (from_state.clone(), read_symbol.clone());
+                if !keys.insert(key.clone()) {
+                    return Err(TmioError::Conflict {
+                        state: from_state,
+                        symbol: read_symbol,
+                    }
+                    .into());
+                }
+                alphabet.insert(read_symbol);
+                alphabet.extend(transition.write_symbol.clone());
+                states.insert(transition.next_state.clone());
+                transitions.push((key, transition));
+            }
+        }
+    }
+    alphabet.insert(blank.clone());
+
+    let running: HashSet<_> = keys.iter().map(|(from, _)| from).collect();
+    let halt_states = states
+        .iter()
+        .filter(|state| !running.contains(state))
+        .cloned()
+        .collect();
+    let input_symbols = alphabet.iter().filter(|&s| *s != blank).cloned().collect();
+    let septuple = Septuple {
+        halt_states,
+        two_way_tape: true,
+        ..Septuple::new(alphabet, blank, input_symbols, states, initial_state)
+    };
+    septuple.with_transitions(transitions)
+}
+
+/// Converte a sétupla para o formato do turingmachine.io, com os estados na ordem de
+/// declaração. Como o formato não define estados de aceitação ou de rejeição, estados
+/// em que a máquina para são exportados sem transições e listados em um comentário.
+/// Transições por classe de símbolos são escritas com a lista dos símbolos que tratam.
+///
+/// O formato não possui transições que mantêm a cabeça parada. Elas são exportadas
+/// movendo para a direita até um estado auxiliar, que move a cabeça de volta para a
+/// esquerda, sem escrever, e segue para o próximo estado da transição original.
+pub fn to_tmio(septuple: &Septuple) -> String {
+    let mut yaml = String::new();
+    let halting = |states: &IndexSet<String>| {
+        states
+            .iter()
+            .map(|s| quote(s))
+            .collect::<Vec<_>>()
+            .join(", ")
+    };
+    for (name, states) in [
+        ("aceitação", &septuple.final_states),
+        ("rejeição", &septuple.reject_states),
+        ("parada", &septuple.halt_states),
+    ] {
+        if !states.is_empty() {
+            writeln!(yaml, "# estados de {name}: {}", halting(states)).unwrap();
+        }
+    }
+    writeln!(yaml, "blank: {}", quote(&septuple.blank_symbol.to_string())).unwrap();
+    writeln!(yaml, "start state: {}", quote(&septuple.initial_state)).unwrap();
+    yaml += "table:\n";
+
+    let transitions = septuple.declared_transitions();
+    // estado auxiliar de cada próximo estado de transições sem movimento
+    let mut returns: IndexMap<&String, String> = IndexMap::new();
+    for (_, _, transition) in &transitions {
+        if transition.move_to.is_none() && !returns.contains_key(&transition.next_state) {
+            let mut name = format!("{}'", transition.next_state);
+            while septuple.states.contains(&name) || returns.values().any(|n| *n == name) {
+                name.push('\'');
+            }
+            returns.insert(&transition.next_state, name);
+        }
+    }
+
+    for state in &septuple.states {
+        writeln!(yaml, "  {}:", quote(state)).unwrap();
+        if septuple.halting_acceptance(state).is_some() {
+            continue;
+        }
+        let from_state = transitions.iter().filter(|(from, _, _)| *from == state);
+        for (_, read_symbol, transition) in from_state {
+            let symbols = match read_symbol {
+                Read::Symbol(symbol) => quote(&symbol.to_string()),
+                Read::Class(_) => {
+                    let symbols = septuple.handled_symbols(state, transition);
+                    if symbols.is_empty() {
+                        continue;
+                    }
+                    let symbols: Vec<_> = symbols.iter().map(|s| quote(&s.to_string())).collect();
+                    format!("[{}]", symbols.join(", "))
+                }
+            };
+            let (direction, next_state) = match transition.move_to {
+                Some(Movement::L) => ("L", &transition.next_state),
+                Some(Movement::R) => ("R", &transition.next_state),
+                None => ("R", &returns[&transition.next_state]),
+            };
+            let write_symbol = transition.write_symbol.as_ref();
+            let instruction = instruction(state, write_symbol, direction, next_state);
+            writeln!(yaml, "    {symbols}: {instruction}").unwrap();
+        }
+    }
+
+    let symbols: Vec<_> = septuple
+        .alphabet
+        .iter()
+        .map(|symbol| quote(symbol.as_str()))
+        .collect();
+    for (next_state, name) in returns {
+        writeln!(yaml, "  {}:", quote(&name)).unwrap();
+        let instruction = instruction(&name, None, "L", next_state);
+        writeln!(yaml, "    [{}]: {instruction}", symbols.join(", ")).unwrap();
+    }
+    yaml
+}
+
+/// Interpreta a instrução aplicada ao ler `read_symbol` no estado `from_state`.
+fn parse_instruction(
+    from_state: &str,
+    read_symbol: &Symbol,
+    instruction: &Value,
+) -> Result<Transition, TmioError> {
+    let invalid = || TmioError::InvalidInstruction {
+        state: from_state.to_string(),
+        symbol: read_symbol.clone(),
+    };
+    let direction = |direction: &str| match direction {
+        "L" => Some(Movement::L),
+        "R" => Some(Movement::R),
+        _ => None,
+    };
+
+    let (move_to, next_state, write_symbol) = match instruction {
+        Value::String(movement) => (direction(movement).ok_or_else(invalid)?, None, None),
+        Value::Mapping(instruction) => {
+            let mut movement = None;
+            let mut write_symbol = None;
+            for (key, value) in instruction {
+                match key.as_str() {
+                    Some("write") => write_symbol = Some(symbol(value)?),
+                    Some(key) if movement.is_none() => {
+                        let next_state = match value {
+                            Value::Null => None,
+                            value => Some(state(value)?),
+                        };
+                        movement = Some((direction(key).ok_or_else(invalid)?, next_state));
+                    }
+                    _ => return Err(invalid()),
+                }
+            }
+            let (move_to, next_state) = movement.ok_or_else(invalid)?;
+            (move_to, next_state, write_symbol)
+        }
+        _ => return Err(invalid()),
+    };
+
+    Ok(Transition {
+        write_symbol,
+        next_state: next_state.unwrap_or_else(|| from_state.to_string()),
+        move_to: Some(move_to),
+    })
+}
+
+/// Escreve, na forma mais curta possível, a instrução que escreve `write_symbol`, move a
+/// cabeça na direção `direction` e segue de `from_state` para `next_state`.
+fn instruction(
+    from_state: &str,
+    write_symbol: Option<&Symbol>,
+    direction: &str,
+    next_state: &str,
+) -> String {
+    let same_state = next_state == from_state;
+    if write_symbol.is_none() && same_state {
+        return direction.to_string();
+    }
+    let mut fields = vec![];
+    if let Some(write_symbol) = write_symbol {
+        fields.push(format!("write: {}", quote(write_symbol.as_str())));
+    }
+    if same_state {
+        fields.push(direction.to_string());
+    } else {
+        fields.push(format!("{direction}: {}", quote(next_state)));
+    }
+    format!("{{{}}}", fields.join(", "))
+}
+
+/// Interpreta um símbolo, que pode ser escrito como texto, número ou booleano. Como no
+/// turingmachine.io, o símbolo deve ter um único caractere.
+fn symbol(value: &Value) -> Result<Symbol, TmioError> {
+    let text = scalar(value).ok_or_else(|| TmioError::InvalidSymbol(describe(value)))?;
+    let mut chars = text.chars();
+    match (chars.next(), chars.next()) {
+        (Some(symbol), None) => Ok(symbol.into()),
+        _ => Err(TmioError::InvalidSymbol(describe(value))),
+    }
+}
+
+/// Interpreta um estado, que pode ser escrito como texto ou número.
+fn state(value: &Value) -> Result<String, TmioError> {
+    scalar(value).ok_or_else(|| TmioError::InvalidState(describe(value)))
+}
+
+fn scalar(value: &Value) -> Option<String> {
+    match value {
+        Value::String(text) => Some(text.clone()),
+        Value::Number(number) => Some(number.to_string()),
+        Value::Bool(boolean) => Some(boolean.to_string()),
+        _ => None,
+    }
+}
+
+/// Descreve um valor inválido em mensagens de erro.
+fn describe(value: &Value) -> String {
+    serde_yaml::to_string(value)
+        .map(|yaml| yaml.trim_end().to_string())
+        .unwrap_or_default()
+}
+
+/// Escreve `text` como uma string YAML entre aspas simples.
+fn quote(text: &str) -> String {
+    format!("'{}'", text.replace('\'', "''"))
+}
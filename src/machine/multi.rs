@@ -0,0 +1,652 @@
+//! Máquinas de Turing com múltiplas fitas. Cada fita possui sua própria cabeça, e cada
+//! transição lê, escreve e move todas as fitas ao mesmo tempo. A entrada é escrita na
+//! primeira fita; as demais começam com um único símbolo branco. Com `two_way_tape`,
+//! todas as fitas são infinitas em ambas as direções.
+
+use std::{collections::HashMap, fmt::Display};
+
+use super::{
+    format::Format,
+    grow_left,
+    sep::{Movement, SepError, Septuple, Transition},
+    symbol::Symbol,
+    Acceptance, InvalidSymbolError, NoUndoError, Rejection,
+};
+
+/// Um mapa de transição multifita. A chave externa é o estado e a interna os símbolos
+/// lidos em cada fita.
+pub type MultiTransitionMap = HashMap<String, HashMap<Vec<Symbol>, MultiTransition>>;
+
+/// Define as ações a serem tomadas em cada fita na aplicação de uma transição.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct MultiTransition {
+    pub write_symbols: Vec<Symbol>,
+    pub next_state: String,
+    pub move_to: Vec<Option<Movement>>,
+}
+
+/// A sétupla usada para definir uma Máquina de Turing multifita.
+#[derive(Clone, Debug)]
+pub struct MultiSeptuple {
+    /// Sétupla com os conjuntos da máquina. Seu mapa de transições é sempre vazio; as
+    /// transições ficam em `transition_map`.
+    pub septuple: Septuple,
+    /// Número de fitas da máquina.
+    pub tapes: usize,
+    pub transition_map: MultiTransitionMap,
+    /// O índice de cada transição no arquivo de origem, por estado e símbolos lidos.
+    pub transition_indices: HashMap<(String, Vec<Symbol>), usize>,
+}
+
+/// Define os erros que podem ocorrer durante o carregamento de uma [`MultiSeptuple`].
+#[derive(Debug)]
+pub enum MultiLoadError {
+    /// O arquivo não é um JSON válido ou não segue o formato da sétupla.
+    Json(serde_json::Error),
+    /// O arquivo não é um YAML válido ou não segue o formato da sétupla.
+    Yaml(serde_yaml::Error),
+    /// O arquivo não é um TOML válido ou não segue o formato da sétupla.
+    Toml(toml::de::Error),
+    /// Máquinas multifita não podem ser definidas no formato.
+    Unsupported(Format),
+    /// A máquina foi definida com zero fitas.
+    NoTapes,
+    /// Há transições inválidas, na ordem em que foram declaradas.
+    Transitions(Vec<MultiTransitionError>),
+}
+
+impl Display for MultiLoadError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            MultiLoadError::Json(err) => write!(f, "{err}"),
+            MultiLoadError::Yaml(err) => write!(f, "{err}"),
+            MultiLoadError::Toml(err) => write!(f, "{err}"),
+            MultiLoadError::Unsupported(format) => {
+                write!(
+                    f,
+                    "máquinas multifita não podem ser definidas no formato {format}"
+                )
+            }
+            MultiLoadError::NoTapes => write!(f, "a máquina deve possuir ao menos uma fita"),
+            MultiLoadError::Transitions(errors) => {
+                let errors: Vec<_> = errors.iter().map(|err| err.to_string()).collect();
+                write!(f, "{}", errors.join("; "))
+            }
+        }
+    }
+}
+
+impl std::error::Error for MultiLoadError {}
+
+/// Define os erros de uma transição multifita encontrados durante o carregamento.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum MultiTransitionError {
+    /// Uma transição não define leitura, escrita e movimento para todas as fitas.
+    TapeCount { index: usize },
+    /// Duas transições partem do mesmo estado e símbolos, mas definem ações diferentes.
+    Conflict { first: usize, second: usize },
+}
+
+impl Display for MultiTransitionError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            MultiTransitionError::TapeCount { index } => {
+                let msg = "não define leitura, escrita e movimento para todas as fitas";
+                write!(f, "transição de índice {index} {msg}")
+            }
+            MultiTransitionError::Conflict { first, second } => {
+                write!(
+                    f,
+                    "transições de índices {first} e {second} são conflitantes"
+                )
+            }
+        }
+    }
+}
+
+impl MultiSeptuple {
+    /// Retorna `true` caso `contents`, no formato `format`, declare o número de fitas da
+    /// máquina, isso é, caso defina uma máquina multifita. Apenas JSON, YAML e TOML
+    /// podem definir máquinas multifita.
+    pub fn declared_in(format: Format, contents: &str) -> bool {
+        match format {
+            Format::Json => serde_json::from_str::<serde_json::Value>(contents)
+                .is_ok_and(|value| value.get("tapes").is_some()),
+            Format::Yaml => serde_yaml::from_str::<serde_yaml::Value>(contents)
+                .is_ok_and(|value| value.get("tapes").is_some()),
+            Format::Toml => toml::from_str::<toml::Table>(contents)
+                .is_ok_and(|value| value.contains_key("tapes")),
+            _ => false,
+        }
+    }
+
+    /// Cria uma sétupla multifita à partir de `contents`, no formato `format`. YAML e TOML
+    /// possuem os mesmos campos do JSON aceito por [`MultiSeptuple::from_json`]; como o
+    /// TOML não possui valor nulo, fitas sem movimento são definidas nele com `"S"`.
+    ///
+    /// # Erros
+    /// Os mesmos de [`MultiSeptuple::from_json`], ou um erro caso o formato não seja
+    /// JSON, YAML ou TOML.
+    pub fn import(format: Format, contents: &str) -> Result<MultiSeptuple, MultiLoadError> {
+        let json: json::JsonMultiSeptuple = match format {
+            Format::Json => serde_json::from_str(contents).map_err(MultiLoadError::Json)?,
+            Format::Yaml => serde_yaml::from_str(contents).map_err(MultiLoadError::Yaml)?,
+            Format::Toml => toml::from_str(contents).map_err(MultiLoadError::Toml)?,
+            _ => return Err(MultiLoadError::Unsupported(format)),
+        };
+        json.into_septuple()
+    }
+
+    /// Cria uma sétupla multifita à partir de um JSON. O formato é o mesmo de
+    /// [`Septuple::from_json`], com o campo adicional `tapes` e transições que definem
+    /// `read_symbols`, `write_symbols` e `move_to` como listas, com um item por fita.
+    /// Transições idênticas são consideradas apenas uma vez.
+    ///
+    /// # Erros
+    /// Além de erros de formato, retorna todas as transições com número incorreto de
+    /// fitas e todos os pares de transições conflitantes.
+    pub fn from_json(json: &str) -> Result<MultiSeptuple, MultiLoadError> {
+        MultiSeptuple::import(Format::Json, json)
+    }
+
+    /// Retorna as transições ordenadas por estado e símbolos lidos, como em
+    /// [`Septuple::sorted_transitions`]. Os símbolos lidos são comparados fita a fita, na
+    /// ordem em que foram declarados em `alphabet`.
+    pub fn sorted_transitions(&self) -> Vec<(&String, &[Symbol], &MultiTransition)> {
+        let base = &self.septuple;
+        let mut transitions: Vec<_> = self
+            .transition_map
+            .iter()
+            .flat_map(|(state, map)| map.iter().map(move |(read, t)| (state, &read[..], t)))
+            .collect();
+        transitions.sort_by_cached_key(|&(state, read, _)| {
+            let state_index = base.states.get_index_of(state).unwrap_or(usize::MAX);
+            let read_indices: Vec<_> = read
+                .iter()
+                .map(|symbol| base.alphabet.get_index_of(symbol).unwrap_or(usize::MAX))
+                .collect();
+            (state_index, state, read_indices, read)
+        });
+        transitions
+    }
+
+    /// Verifica se a sétupla é válida, nos mesmos termos de [`Septuple::valid`]. As
+    /// verificações de cada transição são feitas fita a fita.
+    pub fn valid(&self) -> Result<(), Vec<SepError>> {
+        let mut errors = match self.septuple.valid() {
+            Ok(()) => vec![],
+            Err(errors) => errors,
+        };
+
+        let mut transitions: Vec<_> = self
+            .transition_map
+            .iter()
+            .flat_map(|(state, map)| map.iter().map(move |(read, t)| (state, read, t)))
+            .map(|(state, read, transition)| {
+                let index = self.transition_indices[&(state.clone(), read.clone())];
+                (index, state, read, transition)
+            })
+            .collect();
+        transitions.sort_by_key(|&(index, _, _, _)| index);
+
+        for (index, state, read, transition) in transitions {
+            let mut transition_errors = vec![];
+            for (tape, read_symbol) in read.iter().enumerate() {
+                let single = Transition {
+                    write_symbol: Some(transition.write_symbols[tape].clone()),
+                    next_state: transition.next_state.clone(),
+                    move_to: transition.move_to[tape],
+                };
+                self.septuple.transition_errors(
+                    &(state.clone(), read_symbol.clone()),
+                    &single,
+                    Some(index),
+                    &mut transition_errors,
+                );
+            }
+            // estados inválidos se repetem em todas as fitas
+            for err in transition_errors {
+                if !errors.contains(&err) {
+                    errors.push(err);
+                }
+            }
+        }
+
+        if errors.is_empty() {
+            Ok(())
+        } else {
+            Err(errors)
+        }
+    }
+}
+
+/// Uma Máquina de Turing multifita. As fitas são finitas à esquerda, a não ser que a
+/// sétupla defina `two_way_tape`.
+#[derive(Debug, Clone)]
+pub struct MultiMachine<'machine> {
+    septuple: &'machine MultiSeptuple,
+
+    /// Índice da cabeça em cada fita de `tapes`.
+    current_positions: Vec<usize>,
+    current_state: &'machine String,
+    /// As células de cada fita, precedidas por brancos reservados para o crescimento à
+    /// esquerda, como em [`super::Machine`].
+    tapes: Vec<Vec<Symbol>>,
+    /// Índice, em cada fita de `tapes`, da primeira célula da fita.
+    starts: Vec<usize>,
+    /// Índice, em cada fita de `tapes`, da primeira posição da fita original. Só é
+    /// diferente do início da fita caso ela tenha crescido para a esquerda.
+    origins: Vec<usize>,
+
+    undos: Vec<MultiUndo<'machine>>,
+    /// Número máximo de transições a serem aplicadas. Caso [`None`], não há limite.
+    step_limit: Option<usize>,
+}
+
+impl<'machine> MultiMachine<'machine> {
+    /// Inicializa uma nova máquina multifita, com `tape` na primeira fita. É assumido
+    /// que `septuple` já foi validada. Os símbolos da fita são aceitos como em
+    /// [`super::Machine::new`].
+    ///
+    /// # Erros
+    /// Retorna um erro caso a fita possua símbolos não contidos no alfabeto.
+    pub fn new(
+        septuple: &'machine MultiSeptuple,
+        tape: impl IntoIterator<Item = impl Into<Symbol>>,
+    ) -> Result<MultiMachine<'machine>, InvalidSymbolError> {
+        let sep = &septuple.septuple;
+        let mut tape: Vec<Symbol> = tape.into_iter().map(Into::into).collect();
+        if tape
+            .iter()
+            .any(|symbol| !sep.input_symbols.contains(symbol))
+        {
+            return Err(InvalidSymbolError);
+        }
+        if tape.is_empty() {
+            tape.push(sep.blank_symbol.clone());
+        }
+
+        let mut tapes = vec![tape];
+        tapes.resize(septuple.tapes, vec![sep.blank_symbol.clone()]);
+
+        Ok(MultiMachine {
+            septuple,
+            current_positions: vec![0; septuple.tapes],
+            current_state: &sep.initial_state,
+            tapes,
+            starts: vec![0; septuple.tapes],
+            origins: vec![0; septuple.tapes],
+            undos: vec![],
+            step_limit: None,
+        })
+    }
+
+    /// Define o número máximo de transições que a máquina pode aplicar.
+    pub fn set_step_limit(&mut self, step_limit: Option<usize>) {
+        self.step_limit = step_limit;
+    }
+
+    /// Aplica a transição adequada para o estado atual da máquina. Se o estado atual
+    /// indicar uma aceitação ou rejeição, retorna [`Some`]. Caso a máquina ainda esteja
+    /// processando as fitas, retorna [`None`].
+    pub fn transition(&mut self) -> Option<Acceptance> {
+        if let Some(acceptance) = self.acceptance() {
+            return Some(acceptance);
+        }
+
+        // `acceptance` retorna `None` apenas quando há uma transição aplicável
+        let transition = self.get_transition().unwrap();
+        let undo = self.apply(transition);
+        self.undos.push(undo);
+        None
+    }
+
+    /// Aplica transições até a máquina parar ou atingir o limite de passos.
+    pub fn run(&mut self) -> Acceptance {
+        loop {
+            if let Some(acceptance) = self.transition() {
+                return acceptance;
+            }
+        }
+    }
+
+    /// Desfaz a última transição aplicada na máquina.
+    /// Caso não haja transição para ser desfeita, retornar [`Err`].
+    pub fn undo_transition(&mut self) -> Result<(), NoUndoError> {
+        let undo = match self.undos.pop() {
+            Some(undo) => undo,
+            None => return Err(NoUndoError),
+        };
+
+        for (tape, undo_tape) in undo.tapes.iter().enumerate() {
+            if undo_tape.pop {
+                self.tapes[tape].pop();
+            }
+            match undo_tape.movement {
+                Some(Movement::R) => self.current_positions[tape] += 1,
+                Some(Movement::L) => self.current_positions[tape] -= 1,
+                None => (),
+            }
+            if undo_tape.shift {
+                self.starts[tape] += 1;
+            }
+            let position = self.current_positions[tape];
+            self.tapes[tape][position] = undo_tape.write.clone();
+        }
+        self.current_state = undo.state;
+
+        Ok(())
+    }
+
+    /// Retorna o estado de aceitação de máquina. Caso seja [`None`], a máquina ainda
+    /// está em processamento.
+    pub fn acceptance(&self) -> Option<Acceptance> {
+        let base = &self.septuple.septuple;
+        if let Some(acceptance) = base.halting_acceptance(self.current_state) {
+            return Some(acceptance);
+        }
+        let transition = match self.get_transition() {
+            Some(transition) => transition,
+            None => return Some(Acceptance::Rejected(Rejection::NoTransition)),
+        };
+        let limited_left = !base.two_way_tape
+            && transition
+                .move_to
+                .iter()
+                .zip(&self.current_positions)
+                .any(|(&movement, &position)| movement == Some(Movement::L) && position == 0);
+        if limited_left {
+            return Some(Acceptance::Rejected(Rejection::LeftEnd));
+        }
+        if let Some(step_limit) = self.step_limit {
+            if self.steps() >= step_limit {
+                return Some(Acceptance::Exhausted);
+            }
+        }
+        None
+    }
+
+    /// Retorna, caso exista, a transição para o estado atual da máquina.
+    fn get_transition(&self) -> Option<&'machine MultiTransition> {
+        let read: Vec<Symbol> = self
+            .tapes
+            .iter()
+            .zip(&self.current_positions)
+            .map(|(tape, &position)| tape[position].clone())
+            .collect();
+        self.septuple
+            .transition_map
+            .get(self.current_state)?
+            .get(&read[..])
+    }
+
+    /// Aplica a transição encontrada e retorna um [`MultiUndo`] equivalente.
+    fn apply(&mut self, transition: &'machine MultiTransition) -> MultiUndo<'machine> {
+        let blank = &self.septuple.septuple.blank_symbol;
+        let two_way_tape = self.septuple.septuple.two_way_tape;
+        let mut undo_tapes = Vec::with_capacity(self.tapes.len());
+
+        let tapes = self
+            .tapes
+            .iter_mut()
+            .zip(&mut self.current_positions)
+            .zip(&mut self.starts)
+            .zip(&mut self.origins);
+        for (((tape, position), start), origin) in tapes {
+            let i = undo_tapes.len();
+            let mut undo = UndoTape {
+                pop: false,
+                shift: false,
+                movement: None,
+                write: tape[*position].clone(),
+            };
+
+            tape[*position] = transition.write_symbols[i].clone();
+            match transition.move_to[i] {
+                Some(Movement::R) => {
+                    if *position == tape.len() - 1 {
+                        undo.pop = true;
+                        tape.push(blank.clone());
+                    }
+                    undo.movement = Some(Movement::L);
+                    *position += 1;
+                }
+                Some(Movement::L) => {
+                    if two_way_tape && *position == *start {
+                        if *start == 0 {
+                            let reserved = grow_left(tape, blank);
+                            *position += reserved;
+                            *start += reserved;
+                            *origin += reserved;
+                        }
+                        undo.shift = true;
+                        *start -= 1;
+                    }
+                    undo.movement = Some(Movement::R);
+                    *position -= 1;
+                }
+                None => (),
+            }
+            undo_tapes.push(undo);
+        }
+
+        let undo_state = self.current_state;
+        self.current_state = &transition.next_state;
+        MultiUndo {
+            tapes: undo_tapes,
+            state: undo_state,
+        }
+    }
+
+    // --- getters
+
+    pub fn septuple(&self) -> &MultiSeptuple {
+        self.septuple
+    }
+
+    /// Retorna a posição de cada cabeça relativa ao início da respectiva fita original,
+    /// como em [`super::Machine::current_position`].
+    pub fn current_positions(&self) -> Vec<isize> {
+        self.current_positions
+            .iter()
+            .zip(&self.origins)
+            .map(|(&position, &origin)| position as isize - origin as isize)
+            .collect()
+    }
+
+    /// Retorna o índice de cada cabeça na respectiva fita de [`MultiMachine::tapes`].
+    pub fn head_indices(&self) -> Vec<usize> {
+        self.current_positions
+            .iter()
+            .zip(&self.starts)
+            .map(|(&position, &start)| position - start)
+            .collect()
+    }
+
+    /// Retorna o índice, em cada fita de [`MultiMachine::tapes`], da primeira posição da
+    /// fita original.
+    pub fn origins(&self) -> Vec<usize> {
+        self.origins
+            .iter()
+            .zip(&self.starts)
+            .map(|(&origin, &start)| origin - start)
+            .collect()
+    }
+
+    pub fn current_state(&self) -> &String {
+        self.current_state
+    }
+
+    pub fn tapes(&self) -> Vec<&[Symbol]> {
+        self.tapes
+            .iter()
+            .zip(&self.starts)
+            .map(|(tape, &start)| &tape[start..])
+            .collect()
+    }
+
+    /// Retorna o número de transições aplicadas até o momento.
+    pub fn steps(&self) -> usize {
+        self.undos.len()
+    }
+}
+
+/// Define os passos necessários para desfazer uma transição multifita.
+#[derive(Debug, Clone)]
+struct MultiUndo<'u> {
+    /// As ações a serem desfeitas em cada fita.
+    tapes: Vec<UndoTape>,
+    /// Indica o estado da máquina antes da última transição.
+    state: &'u String,
+}
+
+/// Define os passos necessários para desfazer uma transição em uma única fita.
+#[derive(Debug, Clone)]
+struct UndoTape {
+    /// Caso `true`, um símbolo branco foi adicionado na última transição.
+    pop: bool,
+    /// Caso `true`, a fita cresceu uma célula à esquerda na última transição. A célula
+    /// volta a ser reservada **após** desfazer o último movimento.
+    shift: bool,
+    /// Caso `Some`, é o movimento oposto da última transição.
+    movement: Option<Movement>,
+    /// Indica o símbolo a ser escrito na fita **após** desfazer o último movimento.
+    write: Symbol,
+}
+
+mod json {
+    //! Módulo da representação em JSON da sétupla de uma máquina multifita.
+
+    use std::collections::HashMap;
+
+    use indexmap::IndexSet;
+
+    use serde::Deserialize;
+
+    use super::{
+        MultiLoadError, MultiSeptuple, MultiTransition, MultiTransitionError, MultiTransitionMap,
+    };
+    use crate::machine::{
+        sep::{Movement, Septuple},
+        symbol::Symbol,
+    };
+
+    /// Uma estrutura similar à [`MultiSeptuple`], porém editada para permitir a
+    /// representação em JSON.
+    #[derive(Deserialize)]
+    pub struct JsonMultiSeptuple {
+        alphabet: IndexSet<Symbol>,
+        blank_symbol: Symbol,
+        input_symbols: IndexSet<Symbol>,
+        states: IndexSet<String>,
+        initial_state: String,
+        final_states: IndexSet<String>,
+        #[serde(default)]
+        reject_states: IndexSet<String>,
+        #[serde(default)]
+        halt_states: IndexSet<String>,
+        tapes: usize,
+        transitions: Vec<Transition>,
+        #[serde(default)]
+        two_way_tape: bool,
+    }
+
+    /// Uma transição multifita com o estado e símbolos de leitura necessários para
+    /// aplicá-la.
+    #[derive(Deserialize)]
+    struct Transition {
+        from_state: String,
+        read_symbols: Vec<Symbol>,
+
+        write_symbols: Vec<Symbol>,
+        next_state: String,
+        move_to: Vec<Option<JsonMovement>>,
+    }
+
+    /// O movimento de uma fita. Além de `null`, `"S"` mantém a cabeça parada, já que o
+    /// TOML não possui valor nulo.
+    #[derive(Deserialize)]
+    enum JsonMovement {
+        R,
+        L,
+        S,
+    }
+
+    impl From<JsonMovement> for Option<Movement> {
+        fn from(movement: JsonMovement) -> Self {
+            match movement {
+                JsonMovement::R => Some(Movement::R),
+                JsonMovement::L => Some(Movement::L),
+                JsonMovement::S => None,
+            }
+        }
+    }
+
+    impl JsonMultiSeptuple {
+        /// Converte a representação em JSON para uma [`MultiSeptuple`].
+        pub fn into_septuple(self) -> Result<MultiSeptuple, MultiLoadError> {
+            if self.tapes == 0 {
+                return Err(MultiLoadError::NoTapes);
+            }
+
+            let mut transition_map = MultiTransitionMap::new();
+            let mut transition_indices = HashMap::new();
+            let mut errors = vec![];
+            for (index, transition) in self.transitions.into_iter().enumerate() {
+                let arity_ok = transition.read_symbols.len() == self.tapes
+                    && transition.write_symbols.len() == self.tapes
+                    && transition.move_to.len() == self.tapes;
+                if !arity_ok {
+                    errors.push(MultiTransitionError::TapeCount { index });
+                    continue;
+                }
+
+                let key = (transition.from_state, transition.read_symbols);
+                let value = MultiTransition {
+                    write_symbols: transition.write_symbols,
+                    next_state: transition.next_state,
+                    move_to: transition
+                        .move_to
+                        .into_iter()
+                        .map(|movement| movement.and_then(Option::from))
+                        .collect(),
+                };
+                let by_state = transition_map.entry(key.0.clone()).or_default();
+                match by_state.get(&key.1) {
+                    Some(existing) if *existing == value => (),
+                    Some(_) => errors.push(MultiTransitionError::Conflict {
+                        first: transition_indices[&key],
+                        second: index,
+                    }),
+                    None => {
+                        by_state.insert(key.1.clone(), value);
+                        transition_indices.insert(key, index);
+                    }
+                }
+            }
+            if !errors.is_empty() {
+                return Err(MultiLoadError::Transitions(errors));
+            }
+
+            let septuple = Septuple {
+                final_states: self.final_states,
+                reject_states: self.reject_states,
+                halt_states: self.halt_states,
+                two_way_tape: self.two_way_tape,
+                ..Septuple::new(
+                    self.alphabet,
+                    self.blank_symbol,
+                    self.input_symbols,
+                    self.states,
+                    self.initial_state,
+                )
+            };
+            Ok(MultiSeptuple {
+                septuple,
+                tapes: self.tapes,
+                transition_map,
+                transition_indices,
+            })
+        }
+    }
+}
@@ -0,0 +1,434 @@
+//! Máquinas de Turing não determinísticas. Diferente de uma [`Septuple`], o mapa de
+//! transições de uma [`NondetSeptuple`] permite mais de uma transição por estado e
+//! símbolo. A máquina explora a árvore de configurações em largura e aceita a fita caso
+//! algum dos ramos aceite.
+
+use std::{
+    collections::{hash_map::DefaultHasher, HashMap, VecDeque},
+    hash::{Hash, Hasher},
+};
+
+use super::{
+    sep::{LoadError, Movement, SepError, Septuple, Transition},
+    symbol::Symbol,
+    trace::{Step, StepTransition},
+    Acceptance, InvalidSymbolError, Rejection, DEFAULT_STEP_LIMIT,
+};
+
+/// Um mapa de transição não determinístico. A chave é, respectivamente, estado e
+/// símbolo. O valor são as transições que podem ser aplicadas, na ordem de declaração.
+pub type NondetTransitionMap = HashMap<(String, Symbol), Vec<(usize, Transition)>>;
+
+/// A sétupla usada para definir uma Máquina de Turing não determinística.
+#[derive(Clone, Debug)]
+pub struct NondetSeptuple {
+    /// Sétupla com os conjuntos da máquina. Seu mapa de transições é sempre vazio; as
+    /// transições ficam em `transition_map`.
+    pub septuple: Septuple,
+    /// As transições da máquina, cada uma com seu índice no arquivo de origem.
+    pub transition_map: NondetTransitionMap,
+}
+
+impl NondetSeptuple {
+    /// Cria uma sétupla não determinística à partir de um JSON, no mesmo formato de
+    /// [`Septuple::from_json`]. Transições conflitantes são permitidas; transições
+    /// idênticas são consideradas apenas uma vez.
+    pub fn from_json(json: &str) -> Result<NondetSeptuple, LoadError> {
+        let (septuple, transitions) = Septuple::parts_from_json(json)?;
+
+        let mut transition_map = NondetTransitionMap::new();
+        for (index, (key, transition)) in transitions {
+            let alternatives = transition_map.entry(key).or_default();
+            if alternatives.iter().all(|(_, t)| *t != transition) {
+                alternatives.push((index, transition));
+            }
+        }
+
+        Ok(NondetSeptuple {
+            septuple,
+            transition_map,
+        })
+    }
+
+    /// Verifica se a sétupla é válida, nos mesmos termos de [`Septuple::valid`], exceto
+    /// pelas transições por classe ambíguas, que são permitidas.
+    pub fn valid(&self) -> Result<(), Vec<SepError>> {
+        let mut errors = match self.septuple.valid_nondeterministic() {
+            Ok(()) => vec![],
+            Err(errors) => errors,
+        };
+
+        let mut transitions: Vec<_> = self
+            .transition_map
+            .iter()
+            .flat_map(|(key, alternatives)| alternatives.iter().map(move |(i, t)| (*i, key, t)))
+            .collect();
+        transitions.sort_by_key(|&(index, _, _)| index);
+        for (index, key, transition) in transitions {
+            self.septuple
+                .transition_errors(key, transition, Some(index), &mut errors);
+        }
+
+        if errors.is_empty() {
+            Ok(())
+        } else {
+            Err(errors)
+        }
+    }
+}
+
+/// Uma Máquina de Turing não determinística, finita à esquerda, a não ser que a sétupla
+/// defina uma fita infinita em ambas as direções.
+#[derive(Debug, Clone)]
+pub struct NondetMachine<'machine> {
+    septuple: &'machine NondetSeptuple,
+    tape: Vec<Symbol>,
+    /// Número máximo de configurações exploradas. Caso [`None`], não há limite.
+    limit: Option<usize>,
+}
+
+/// O resultado da exploração de uma [`NondetMachine`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct NondetRun {
+    /// [`Acceptance::Accepted`] caso algum ramo aceite, [`Acceptance::Looping`] caso
+    /// nenhum ramo aceite e algum repita uma configuração, e [`Acceptance::Exhausted`]
+    /// caso o limite de configurações seja atingido. Caso todos os ramos parem sem
+    /// aceitar, é o resultado do primeiro ramo a parar, na ordem de exploração.
+    pub acceptance: Acceptance,
+    /// Número de configurações exploradas.
+    pub explored: usize,
+    /// O caminho do ramo que aceitou a fita, da configuração inicial à final.
+    pub path: Option<Vec<Step>>,
+}
+
+/// Uma configuração na árvore de exploração. A fita do nó não é guardada: ela só existe
+/// enquanto o nó aguarda ser explorado, e é reconstruída à partir da fita inicial e das
+/// transições aplicadas quando necessário; veja [`NondetMachine::replay`].
+struct Node<'n> {
+    state: &'n String,
+    /// Posição da cabeça, relativa ao início da fita original.
+    position: isize,
+    /// Índice do nó pai e a transição aplicada nele, caso exista.
+    parent: Option<(usize, &'n Transition)>,
+    depth: usize,
+}
+
+/// A fita de um ramo da exploração.
+#[derive(Clone)]
+struct Tape {
+    cells: Vec<Symbol>,
+    /// Índice, em `cells`, da primeira posição da fita original.
+    origin: usize,
+}
+
+impl Tape {
+    /// Retorna o índice, em `cells`, da posição `position`.
+    fn index(&self, position: isize) -> usize {
+        (position + self.origin as isize) as usize
+    }
+}
+
+/// A configuração de um nó, desconsiderando os brancos nas extremidades da fita, além
+/// da cabeça: estado, posição da cabeça, posição do primeiro símbolo e os símbolos.
+type Configuration<'c> = (&'c String, isize, isize, &'c [Symbol]);
+
+impl<'machine> NondetMachine<'machine> {
+    /// Inicializa uma nova máquina não determinística, com o limite de configurações
+    /// [`DEFAULT_STEP_LIMIT`]. É assumido que `septuple` já foi validada. Os símbolos
+    /// da fita são aceitos como em [`super::Machine::new`].
+    ///
+    /// # Erros
+    /// Retorna um erro caso a fita possua símbolos não contidos no alfabeto.
+    pub fn new(
+        septuple: &'machine NondetSeptuple,
+        tape: impl IntoIterator<Item = impl Into<Symbol>>,
+    ) -> Result<NondetMachine<'machine>, InvalidSymbolError> {
+        let sep = &septuple.septuple;
+        let mut tape: Vec<Symbol> = tape.into_iter().map(Into::into).collect();
+        if tape
+            .iter()
+            .any(|symbol| !sep.input_symbols.contains(symbol))
+        {
+            return Err(InvalidSymbolError);
+        }
+        if tape.is_empty() {
+            tape.push(sep.blank_symbol.clone());
+        }
+
+        Ok(NondetMachine {
+            septuple,
+            tape,
+            limit: Some(DEFAULT_STEP_LIMIT),
+        })
+    }
+
+    /// Define o número máximo de configurações exploradas.
+    pub fn set_limit(&mut self, limit: Option<usize>) {
+        self.limit = limit;
+    }
+
+    /// Explora, em largura, as configurações alcançáveis à partir da fita inicial até
+    /// encontrar um ramo que aceite, esgotar os ramos ou atingir o limite.
+    ///
+    /// Apenas os nós ainda não explorados possuem uma fita; as configurações visitadas são
+    /// guardadas por seu hash e comparadas por completo apenas quando os hashes coincidem.
+    pub fn run(&self) -> NondetRun {
+        let sep = &self.septuple.septuple;
+        let mut nodes = vec![Node {
+            state: &sep.initial_state,
+            position: 0,
+            parent: None,
+            depth: 0,
+        }];
+        let initial = Tape {
+            cells: self.tape.clone(),
+            origin: 0,
+        };
+        let mut queue = VecDeque::from([(0, initial)]);
+        let mut visited: HashMap<u64, Vec<usize>> = HashMap::new();
+        let mut repeated = false;
+        let mut stopped = None;
+        let mut explored = 0;
+
+        while let Some((current, tape)) = queue.pop_front() {
+            if let Some(limit) = self.limit {
+                if explored >= limit {
+                    return self.finish(Acceptance::Exhausted, explored, &nodes, None);
+                }
+            }
+            explored += 1;
+
+            let node = &nodes[current];
+            match sep.halting_acceptance(node.state) {
+                Some(Acceptance::Accepted) => {
+                    return self.finish(Acceptance::Accepted, explored, &nodes, Some(current));
+                }
+                Some(acceptance) => {
+                    stopped.get_or_insert(acceptance);
+                    continue;
+                }
+                None => (),
+            }
+
+            let configuration = self.configuration(node, &tape);
+            let seen = visited.entry(hash(&configuration)).or_default();
+            let repeats = seen.iter().any(|&other| {
+                let other_tape = self.replay(&nodes, other, |_, _| ());
+                self.configuration(&nodes[other], &other_tape) == configuration
+            });
+            if repeats {
+                // ramos diferentes podem convergir na mesma configuração; apenas a
+                // repetição de um ancestral indica um laço
+                repeated |= self.in_cycle(&nodes, current, configuration);
+                continue;
+            }
+            seen.push(current);
+
+            // transições por classe só são usadas na ausência de transições exatas, e
+            // todas as classes de maior precedência que se aplicam ao símbolo geram ramos
+            let symbol = &tape.cells[tape.index(node.position)];
+            let alternatives: Vec<_> = match self
+                .septuple
+                .transition_map
+                .get(&(node.state.clone(), symbol.clone()))
+            {
+                Some(alternatives) => alternatives.iter().map(|(_, t)| t).collect(),
+                None => self.septuple.septuple.class_transitions(node.state, symbol),
+            };
+            if alternatives.is_empty() {
+                stopped.get_or_insert(Acceptance::Rejected(Rejection::NoTransition));
+                continue;
+            }
+
+            // o último ramo reaproveita a fita do nó, que não é mais necessária
+            let mut tape = Some(tape);
+            let last = alternatives.len() - 1;
+            for (i, transition) in alternatives.into_iter().enumerate() {
+                let branch = if i == last {
+                    tape.take().unwrap()
+                } else {
+                    tape.clone().unwrap()
+                };
+                match self.apply(&nodes[current], current, branch, transition) {
+                    Some((child, tape)) => {
+                        queue.push_back((nodes.len(), tape));
+                        nodes.push(child);
+                    }
+                    None => {
+                        stopped.get_or_insert(Acceptance::Rejected(Rejection::LeftEnd));
+                    }
+                }
+            }
+        }
+
+        let acceptance = match stopped {
+            _ if repeated => Acceptance::Looping,
+            Some(acceptance) => acceptance,
+            None => Acceptance::Rejected(Rejection::NoTransition),
+        };
+        self.finish(acceptance, explored, &nodes, None)
+    }
+
+    /// Aplica `transition` no nó `node`, de índice `index` e fita `tape`, retornando o nó
+    /// filho e sua fita. Retorna [`None`] caso a transição mova a cabeça para a esquerda
+    /// da posição zero em uma fita finita à esquerda.
+    fn apply(
+        &self,
+        node: &Node<'machine>,
+        index: usize,
+        mut tape: Tape,
+        transition: &'machine Transition,
+    ) -> Option<(Node<'machine>, Tape)> {
+        let mut cell = tape.index(node.position);
+        tape.cells[cell] = transition.written(&tape.cells[cell]);
+        match transition.move_to {
+            Some(Movement::R) => {
+                cell += 1;
+                if cell == tape.cells.len() {
+                    tape.cells.push(self.septuple.septuple.blank_symbol.clone());
+                }
+            }
+            Some(Movement::L) if cell == 0 && self.septuple.septuple.two_way_tape => {
+                tape.cells
+                    .insert(0, self.septuple.septuple.blank_symbol.clone());
+                tape.origin += 1;
+            }
+            Some(Movement::L) => cell = cell.checked_sub(1)?,
+            None => (),
+        }
+
+        let child = Node {
+            state: &transition.next_state,
+            position: cell as isize - tape.origin as isize,
+            parent: Some((index, transition)),
+            depth: node.depth + 1,
+        };
+        Some((child, tape))
+    }
+
+    /// Reconstrói a fita do nó `index`, aplicando à fita inicial as transições do
+    /// caminho até ele. `visit` é chamada com cada nó do caminho e sua fita, da raiz ao
+    /// próprio nó.
+    fn replay(
+        &self,
+        nodes: &[Node<'machine>],
+        index: usize,
+        mut visit: impl FnMut(usize, &Tape),
+    ) -> Tape {
+        let mut path = vec![index];
+        while let Some((parent, _)) = nodes[*path.last().unwrap()].parent {
+            path.push(parent);
+        }
+
+        let mut tape = Tape {
+            cells: self.tape.clone(),
+            origin: 0,
+        };
+        let mut previous = None;
+        for &current in path.iter().rev() {
+            if let (Some(previous), Some((_, transition))) = (previous, nodes[current].parent) {
+                // o caminho já foi explorado; a transição não pode falhar
+                tape = self
+                    .apply(&nodes[previous], previous, tape, transition)
+                    .unwrap()
+                    .1;
+            }
+            visit(current, &tape);
+            previous = Some(current);
+        }
+        tape
+    }
+
+    /// Retorna `true` caso `configuration`, do nó `index`, seja igual à configuração de
+    /// algum de seus ancestrais.
+    fn in_cycle(
+        &self,
+        nodes: &[Node<'machine>],
+        index: usize,
+        configuration: Configuration,
+    ) -> bool {
+        let Some((parent, _)) = nodes[index].parent else {
+            return false;
+        };
+        let mut found = false;
+        self.replay(nodes, parent, |ancestor, tape| {
+            found |= self.configuration(&nodes[ancestor], tape) == configuration;
+        });
+        found
+    }
+
+    /// Retorna a configuração do nó `node`, de fita `tape`. As posições são relativas ao
+    /// início da fita original.
+    fn configuration<'c>(&'c self, node: &Node<'c>, tape: &'c Tape) -> Configuration<'c> {
+        let blank = &self.septuple.septuple.blank_symbol;
+        let position = tape.index(node.position);
+        let first = tape.cells.iter().position(|symbol| symbol != blank);
+        let start = first.map_or(position, |first| first.min(position));
+        let used = tape
+            .cells
+            .iter()
+            .rposition(|symbol| symbol != blank)
+            .map_or(0, |last| last + 1);
+        let end = used.max(position + 1);
+        let origin = tape.origin as isize;
+        (
+            node.state,
+            node.position,
+            start as isize - origin,
+            &tape.cells[start..end],
+        )
+    }
+
+    /// Constrói o resultado da exploração. Caso `accepted` seja [`Some`], o caminho até
+    /// o nó de aceitação é reconstruído à partir dos pais de cada nó.
+    fn finish(
+        &self,
+        acceptance: Acceptance,
+        explored: usize,
+        nodes: &[Node<'machine>],
+        accepted: Option<usize>,
+    ) -> NondetRun {
+        let path = accepted.map(|accepted| {
+            let mut path = vec![];
+            // símbolo lido pelo nó anterior do caminho
+            let mut read: Option<Symbol> = None;
+            self.replay(nodes, accepted, |current, tape| {
+                let node = &nodes[current];
+                let transition = node.parent.map(|(parent, transition)| {
+                    // o nó anterior do caminho é o pai; `read` foi definido por ele
+                    let read_symbol = read.take().unwrap();
+                    StepTransition {
+                        from_state: nodes[parent].state.clone(),
+                        write_symbol: transition.written(&read_symbol),
+                        read_symbol,
+                        move_to: transition.move_to,
+                    }
+                });
+                read = Some(tape.cells[tape.index(node.position)].clone());
+                path.push(Step {
+                    step: node.depth,
+                    state: node.state.clone(),
+                    position: node.position,
+                    tape_start: -(tape.origin as isize),
+                    tape: tape.cells.clone(),
+                    transition,
+                });
+            });
+            path
+        });
+
+        NondetRun {
+            acceptance,
+            explored,
+            path,
+        }
+    }
+}
+
+/// Retorna o hash de uma configuração, usado para encontrar configurações repetidas sem
+/// guardar suas fitas.
+fn hash(configuration: &Configuration) -> u64 {
+    let mut hasher = DefaultHasher::new();
+    configuration.hash(&mut hasher);
+    hasher.finish()
+}
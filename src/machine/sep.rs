@@ -1,133 +1,868 @@
-use std::{
-    collections::{HashMap, HashSet},
-    fmt::Display,
+#[cfg(test)]
+mod test;
+
+use std::{collections::HashMap, fmt::Display, ptr};
+
+use indexmap::IndexSet;
+use serde::{
+    de::{self, IntoDeserializer},
+    ser::SerializeMap,
+    Deserialize, Deserializer, Serialize, Serializer,
 };
 
-use serde::Deserialize;
+use super::{symbol::Symbol, Acceptance, Rejection};
 
 use self::json::JsonSeptuple;
 pub use self::transition_key::TransitionKey;
+use super::format::{
+    jflap::JflapError, morphett::MorphettError, text::TextError, tmio::TmioError, Format,
+};
 
 /// Uma mapa de transição de estados.
 /// A chave é, respectivamente, estado e símbolo. O valor é a transição a ser aplicada.
-pub type TransitionMap = HashMap<(String, char), Transition>;
+pub type TransitionMap = HashMap<(String, Symbol), Transition>;
+
+/// Uma transição junto de sua chave (estado e símbolo lido), como declarada no arquivo
+/// de origem.
+pub type KeyedTransition = ((String, Symbol), Transition);
+
+/// Mapeia cada chave de um [`TransitionMap`] ao índice da transição no arquivo de origem.
+pub type TransitionIndices = HashMap<(String, Symbol), usize>;
+
+/// Mapeia cada estado às transições por classe de símbolos que partem dele, na ordem em
+/// que foram declaradas.
+pub type ClassMap = HashMap<String, Vec<ClassTransition>>;
+
+/// Uma transição, exata ou por classe, junto do estado e do símbolo lido, como declarada
+/// no arquivo de origem.
+pub type DeclaredTransition = ((String, Read), Transition);
 
 /// A sétupla usada para definir uma Máquina de Turing.
 /// <https://en.wikipedia.org/wiki/Turing_machine#Formal_definition>
 #[derive(Clone, Debug)]
 pub struct Septuple {
     /// Símbolos do alfabeto da fita.
-    pub alphabet: HashSet<char>,
+    pub alphabet: IndexSet<Symbol>,
     /// Símbolo branco -- o único símbolo que pode ocorrer infinitamente em qualquer etapa
     /// durante a computação
-    pub blank_symbol: char,
+    pub blank_symbol: Symbol,
     /// O conjunto de símbolos que pode estar inicialmente presente na fita.
-    pub input_symbols: HashSet<char>,
+    pub input_symbols: IndexSet<Symbol>,
 
     /// O conjunto de estados que a máquina pode tomar.
-    pub states: HashSet<String>,
+    pub states: IndexSet<String>,
     /// O estado inicial da máquina.
     pub initial_state: String,
     /// O conjunto de estados de aceitação da máquina.
-    pub final_states: HashSet<String>,
+    pub final_states: IndexSet<String>,
+    /// O conjunto de estados de rejeição da máquina.
+    pub reject_states: IndexSet<String>,
+    /// O conjunto de estados em que a máquina para sem aceitar ou rejeitar a fita, usado
+    /// por máquinas que computam funções.
+    pub halt_states: IndexSet<String>,
 
     /// Um _mapa_ de transição, usado para representar a _função_ de transição da máquina.
     pub transition_map: TransitionMap,
+    /// Caso `true`, a fita é infinita em ambas as direções, crescendo com brancos à
+    /// esquerda. Caso contrário, a fita é finita à esquerda.
+    pub two_way_tape: bool,
+    /// O índice de cada transição no arquivo de origem da sétupla. Transições criadas
+    /// diretamente no mapa não possuem índice.
+    pub transition_indices: TransitionIndices,
+    /// A linha de cada transição no arquivo de origem, por índice. É vazio caso o formato
+    /// de origem não seja organizado em linhas.
+    pub(crate) transition_lines: Vec<usize>,
+    /// As transições aplicadas a classes de símbolos, usadas quando não há uma transição
+    /// exata em `transition_map`. Veja [`Septuple::find_transition`].
+    pub class_map: ClassMap,
 }
 
 impl Septuple {
-    /// Cria uma sétupla à partir de um JSON.
-    pub fn from_json(json: &str) -> Result<Septuple, serde_json::Error> {
-        let json_septuple: JsonSeptuple = serde_json::from_str(json)?;
-        Ok(Septuple::from(json_septuple))
+    /// Cria uma sétupla sem transições, estados finais, de rejeição ou de parada, com uma
+    /// fita finita à esquerda. Os demais campos podem ser definidos pela sintaxe de
+    /// atualização de structs, e as transições por [`Septuple::with_transitions`].
+    pub fn new(
+        alphabet: IndexSet<Symbol>,
+        blank_symbol: Symbol,
+        input_symbols: IndexSet<Symbol>,
+        states: IndexSet<String>,
+        initial_state: String,
+    ) -> Septuple {
+        Septuple {
+            alphabet,
+            blank_symbol,
+            input_symbols,
+            states,
+            initial_state,
+            final_states: IndexSet::new(),
+            reject_states: IndexSet::new(),
+            halt_states: IndexSet::new(),
+            transition_map: TransitionMap::new(),
+            two_way_tape: false,
+            transition_indices: TransitionIndices::new(),
+            transition_lines: vec![],
+            class_map: ClassMap::new(),
+        }
+    }
+
+    /// Cria uma sétupla à partir de um JSON. Transições duplicadas são ignoradas; para
+    /// obtê-las, use [`Septuple::from_json_with_warnings`].
+    pub fn from_json(json: &str) -> Result<Septuple, LoadError> {
+        Ok(Septuple::from_json_with_warnings(json)?.0)
+    }
+
+    /// Cria uma sétupla à partir de um JSON, retornando também as transições declaradas
+    /// mais de uma vez de forma idêntica.
+    ///
+    /// # Erros
+    /// Além de erros de sintaxe, retorna um erro caso duas transições partam do mesmo
+    /// estado e símbolo mas definam ações diferentes.
+    pub fn from_json_with_warnings(
+        json: &str,
+    ) -> Result<(Septuple, Vec<TransitionClash>), LoadError> {
+        let json_septuple: JsonSeptuple = serde_json::from_str(json).map_err(LoadError::Json)?;
+        json_septuple.into_septuple()
+    }
+
+    /// Cria uma sétupla à partir de um YAML com os mesmos campos do JSON aceito por
+    /// [`Septuple::from_json`], retornando também as transições duplicadas.
+    ///
+    /// # Erros
+    /// Os mesmos de [`Septuple::from_json_with_warnings`].
+    pub fn from_yaml_with_warnings(
+        yaml: &str,
+    ) -> Result<(Septuple, Vec<TransitionClash>), LoadError> {
+        let json_septuple: JsonSeptuple = serde_yaml::from_str(yaml).map_err(LoadError::Yaml)?;
+        json_septuple.into_septuple()
+    }
+
+    /// Cria uma sétupla à partir de um TOML com os mesmos campos do JSON aceito por
+    /// [`Septuple::from_json`], retornando também as transições duplicadas. Transições
+    /// sem movimento omitem o campo `move_to`.
+    ///
+    /// # Erros
+    /// Os mesmos de [`Septuple::from_json_with_warnings`].
+    pub fn from_toml_with_warnings(
+        toml: &str,
+    ) -> Result<(Septuple, Vec<TransitionClash>), LoadError> {
+        let json_septuple: JsonSeptuple = toml::from_str(toml).map_err(LoadError::Toml)?;
+        json_septuple.into_septuple()
+    }
+
+    /// Substitui as transições de uma sétupla pelas transições `transitions`, declaradas
+    /// nessa ordem no arquivo de origem. Retorna também as transições duplicadas.
+    ///
+    /// # Erros
+    /// Retorna um erro caso duas transições partam do mesmo estado e símbolo mas definam
+    /// ações diferentes.
+    pub(crate) fn with_transitions(
+        self,
+        transitions: Vec<KeyedTransition>,
+    ) -> Result<(Septuple, Vec<TransitionClash>), LoadError> {
+        let declarations = transitions
+            .into_iter()
+            .map(|((state, symbol), transition)| ((state, Read::Symbol(symbol)), transition))
+            .collect();
+        self.with_declarations(declarations)
+    }
+
+    /// Substitui as transições de uma sétupla pelas transições exatas e por classe
+    /// `declarations`, declaradas nessa ordem no arquivo de origem. Retorna também as
+    /// transições exatas duplicadas.
+    ///
+    /// # Erros
+    /// Os mesmos de [`Septuple::with_transitions`].
+    pub(crate) fn with_declarations(
+        mut self,
+        declarations: Vec<DeclaredTransition>,
+    ) -> Result<(Septuple, Vec<TransitionClash>), LoadError> {
+        let (class_map, transitions) = split_declarations(declarations);
+        let (transition_map, transition_indices, duplicates) =
+            build_transition_map(transitions, &self.transition_lines)?;
+        self.transition_map = transition_map;
+        self.transition_indices = transition_indices;
+        self.class_map = class_map;
+        Ok((self, duplicates))
+    }
+
+    /// Converte a sétupla para JSON, no mesmo formato aceito por [`Septuple::from_json`].
+    /// A saída é formatada e estável: conjuntos seguem a ordem de declaração e as
+    /// transições seguem a ordem do arquivo de origem.
+    pub fn to_json(&self) -> String {
+        // a serialização de `JsonSeptuple` não falha: todas as chaves são strings
+        serde_json::to_string_pretty(self).unwrap()
+    }
+
+    /// Converte a sétupla para YAML, nos mesmos termos de [`Septuple::to_json`].
+    pub fn to_yaml(&self) -> String {
+        // a serialização de `JsonSeptuple` não falha: todas as chaves são strings
+        serde_yaml::to_string(self).unwrap()
+    }
+
+    /// Converte a sétupla para TOML, nos mesmos termos de [`Septuple::to_json`].
+    pub fn to_toml(&self) -> String {
+        // as transições são o único campo do tipo tabela e são declaradas por último em
+        // `JsonSeptuple`; os demais campos são textos, listas de textos e booleanos
+        toml::to_string(self).expect("a sétupla possui apenas valores representáveis em TOML")
+    }
+
+    /// Cria, à partir de um JSON, uma sétupla sem transições exatas junto de todas as
+    /// transições exatas declaradas, cada uma com seu índice no arquivo. As transições
+    /// por classe são mantidas na sétupla. Usado por máquinas que permitem transições
+    /// conflitantes.
+    pub(crate) fn parts_from_json(
+        json: &str,
+    ) -> Result<(Septuple, Vec<(usize, KeyedTransition)>), LoadError> {
+        let json_septuple: JsonSeptuple = serde_json::from_str(json).map_err(LoadError::Json)?;
+        let (mut septuple, declarations) = json_septuple.into_parts();
+        let (class_map, transitions) = split_declarations(declarations);
+        septuple.class_map = class_map;
+        Ok((septuple, transitions))
     }
 
     /// Verifica se a sétupla é válida. As condições para ela ser inválida são descreitas
-    /// pelos membros de [`SepError`].
-    pub fn valid(&self) -> Result<(), SepError> {
+    /// pelos membros de [`SepError`]. Todos os erros encontrados são retornados, na
+    /// ordem em que aparecem na definição da sétupla.
+    pub fn valid(&self) -> Result<(), Vec<SepError>> {
+        self.validate(true)
+    }
+
+    /// Verifica se a sétupla é válida como parte de uma máquina não determinística, que
+    /// permite transições por classe ambíguas. Veja [`Septuple::valid`].
+    pub(crate) fn valid_nondeterministic(&self) -> Result<(), Vec<SepError>> {
+        self.validate(false)
+    }
+
+    /// Verifica a sétupla, incluindo as ambiguidades entre transições por classe caso
+    /// `ambiguity` seja `true`.
+    fn validate(&self, ambiguity: bool) -> Result<(), Vec<SepError>> {
+        let mut errors = vec![];
+
         if !self.alphabet.contains(&self.blank_symbol) {
-            return Err(SepError::BlankNotInAlph);
+            errors.push(SepError::BlankNotInAlph(self.blank_symbol.clone()));
         }
-        if !self.input_symbols.is_subset(&self.alphabet) {
-            return Err(SepError::InputNotSubAlph);
+        for symbol in self.input_symbols.difference(&self.alphabet) {
+            errors.push(SepError::InputNotSubAlph(symbol.clone()));
         }
         if !self.states.contains(&self.initial_state) {
-            return Err(SepError::InitNotInStates);
+            errors.push(SepError::InitNotInStates(self.initial_state.clone()));
+        }
+        for state in self.final_states.difference(&self.states) {
+            errors.push(SepError::FinalNotSubStates(state.clone()));
+        }
+        for state in self.reject_states.difference(&self.states) {
+            errors.push(SepError::RejectNotSubStates(state.clone()));
+        }
+        for state in self.halt_states.difference(&self.states) {
+            errors.push(SepError::HaltNotSubStates(state.clone()));
         }
-        if !self.final_states.is_subset(&self.states) {
-            return Err(SepError::FinalNotSubStates);
+        let overlapping: IndexSet<_> = self
+            .final_states
+            .intersection(&self.reject_states)
+            .chain(self.final_states.intersection(&self.halt_states))
+            .chain(self.reject_states.intersection(&self.halt_states))
+            .collect();
+        for state in overlapping {
+            errors.push(SepError::OverlappingHaltStates(state.clone()));
         }
 
-        for key in self.transition_map.keys() {
-            let (state, symbol) = key;
+        // transições com índice vêm primeiro, na ordem do arquivo
+        let mut keys: Vec<_> = self.transition_map.keys().collect();
+        keys.sort_by_key(|&key| {
+            let index = self.transition_indices.get(key);
+            (index.is_none(), index, key)
+        });
+
+        for key in keys {
             let transition = self.transition_map.get(key).unwrap();
+            let index = self.transition_indices.get(key).copied();
+            self.transition_errors(key, transition, index, &mut errors);
+        }
+        self.class_errors(ambiguity, &mut errors);
+
+        if errors.is_empty() {
+            Ok(())
+        } else {
+            Err(errors)
+        }
+    }
+
+    /// Retorna as transições ordenadas por estado e símbolo, na ordem em que foram
+    /// declarados em `states` e `alphabet`. Estados e símbolos não declarados vêm por
+    /// último, em ordem crescente. As transições por classe de cada estado vêm após as
+    /// transições exatas, em ordem decrescente de precedência.
+    pub fn sorted_transitions(&self) -> Vec<(&String, Read, &Transition)> {
+        let mut transitions: Vec<_> = self
+            .transition_map
+            .iter()
+            .map(|((state, symbol), transition)| {
+                let symbol_index = self.alphabet.get_index_of(symbol).unwrap_or(usize::MAX);
+                let order = (0, symbol_index, 0, Some(symbol));
+                (order, state, Read::Symbol(symbol.clone()), transition)
+            })
+            .chain(self.class_map.iter().flat_map(|(state, classes)| {
+                classes.iter().map(move |class| {
+                    let precedence = 2 - class.class.precedence() as usize;
+                    let order = (1, precedence, class.index.unwrap_or(usize::MAX), None);
+                    (
+                        order,
+                        state,
+                        Read::Class(class.class.clone()),
+                        &class.transition,
+                    )
+                })
+            }))
+            .collect();
+        transitions.sort_by_key(|&(order, state, _, _)| {
+            let state_index = self.states.get_index_of(state).unwrap_or(usize::MAX);
+            (state_index, state, order)
+        });
+        transitions
+            .into_iter()
+            .map(|(_, state, read, transition)| (state, read, transition))
+            .collect()
+    }
 
-            if !self.states.contains(state) {
-                return Err(SepError::TransitionStateNotInStates);
+    /// Retorna as transições na ordem do arquivo de origem. Transições sem índice vêm
+    /// por último, na ordem de [`Septuple::sorted_transitions`].
+    pub fn declared_transitions(&self) -> Vec<(&String, Read, &Transition)> {
+        let mut transitions = self.sorted_transitions();
+        transitions.sort_by_key(|(state, read, transition)| {
+            let index = self.declaration_index(state, read, transition);
+            (index.is_none(), index)
+        });
+        transitions
+    }
+
+    /// Retorna as transições com cada classe de símbolos expandida nos símbolos do
+    /// alfabeto que ela efetivamente trata, e com o símbolo escrito explícito. Usado por
+    /// formatos que não suportam classes de símbolos. A ordem é a de
+    /// [`Septuple::declared_transitions`].
+    pub fn concrete_transitions(&self) -> Vec<KeyedTransition> {
+        let mut transitions = vec![];
+        for (state, read, transition) in self.declared_transitions() {
+            let symbols = match read {
+                Read::Symbol(symbol) => vec![symbol],
+                Read::Class(_) => self.handled_symbols(state, transition),
+            };
+            for symbol in symbols {
+                let transition = Transition {
+                    write_symbol: Some(transition.written(&symbol)),
+                    ..transition.clone()
+                };
+                transitions.push(((state.clone(), symbol), transition));
             }
+        }
+        transitions
+    }
+
+    /// Retorna os símbolos do alfabeto para os quais `transition`, partindo de `state`,
+    /// é a transição aplicada por [`Septuple::find_transition`].
+    pub fn handled_symbols(&self, state: &String, transition: &Transition) -> Vec<Symbol> {
+        self.alphabet
+            .iter()
+            .filter(|symbol| {
+                self.find_transition(state, symbol)
+                    .is_some_and(|found| ptr::eq(found, transition))
+            })
+            .cloned()
+            .collect()
+    }
+
+    /// Retorna a transição aplicada ao ler `symbol` no estado `state`.
+    ///
+    /// Uma transição exata sempre tem precedência. Caso não exista, é usada a transição
+    /// por classe de maior precedência que contém o símbolo: primeiro conjuntos (`in`),
+    /// depois complementos (`except`) e por último `any`. A validação da sétupla garante
+    /// que não há duas transições diferentes de mesma precedência para o mesmo símbolo.
+    pub fn find_transition(&self, state: &String, symbol: &Symbol) -> Option<&Transition> {
+        let key = (state, symbol);
+        if let Some(transition) = self.transition_map.get(&key as &dyn TransitionKey) {
+            return Some(transition);
+        }
+
+        let mut found: Option<&ClassTransition> = None;
+        for class in self.class_map.get(state)? {
+            let higher =
+                found.is_none_or(|found| class.class.precedence() > found.class.precedence());
+            if class.class.matches(symbol) && higher {
+                found = Some(class);
+            }
+        }
+        found.map(|class| &class.transition)
+    }
+
+    /// Retorna todas as transições por classe de maior precedência que se aplicam a
+    /// `symbol` em `state`, na ordem de declaração. Usado por máquinas não
+    /// determinísticas, em que classes de mesma precedência podem se sobrepor.
+    pub(crate) fn class_transitions(&self, state: &String, symbol: &Symbol) -> Vec<&Transition> {
+        let matching: Vec<_> = self
+            .class_map
+            .get(state)
+            .into_iter()
+            .flatten()
+            .filter(|class| class.class.matches(symbol))
+            .collect();
+        let highest = matching.iter().map(|class| class.class.precedence()).max();
+        matching
+            .into_iter()
+            .filter(|class| Some(class.class.precedence()) == highest)
+            .map(|class| &class.transition)
+            .collect()
+    }
+
+    /// Retorna o resultado do processamento caso `state` seja um estado final, de
+    /// rejeição ou de parada. Caso contrário, retorna [`None`].
+    pub fn halting_acceptance(&self, state: &String) -> Option<Acceptance> {
+        if self.final_states.contains(state) {
+            Some(Acceptance::Accepted)
+        } else if self.reject_states.contains(state) {
+            Some(Acceptance::Rejected(Rejection::RejectState))
+        } else if self.halt_states.contains(state) {
+            Some(Acceptance::Halted)
+        } else {
+            None
+        }
+    }
+
+    /// Adiciona em `errors` os erros de uma transição com chave `key`, declarada no
+    /// índice `index` do arquivo de origem.
+    pub(crate) fn transition_errors(
+        &self,
+        key: &(String, Symbol),
+        transition: &Transition,
+        index: Option<usize>,
+        errors: &mut Vec<SepError>,
+    ) {
+        let (state, symbol) = key;
+        let location = self.location(index);
+
+        if !self.states.contains(state) {
+            errors.push(SepError::TransitionStateNotInStates {
+                state: state.clone(),
+                location,
+            });
+        }
+        if !self.alphabet.contains(symbol) {
+            errors.push(SepError::TransitionSymbolNotInAlphabet {
+                symbol: symbol.clone(),
+                location,
+            });
+        }
+
+        self.action_errors(transition, location, errors);
+    }
+
+    /// Adiciona em `errors` os erros das ações de uma transição declarada em `location`
+    /// no arquivo de origem.
+    fn action_errors(
+        &self,
+        transition: &Transition,
+        location: Option<Location>,
+        errors: &mut Vec<SepError>,
+    ) {
+        if !self.states.contains(&transition.next_state) {
+            errors.push(SepError::TransitionStateNotInStates {
+                state: transition.next_state.clone(),
+                location,
+            });
+        }
+        if let Some(symbol) = &transition.write_symbol {
             if !self.alphabet.contains(symbol) {
-                return Err(SepError::TransitionSymbolNotInAlphabet);
+                errors.push(SepError::TransitionSymbolNotInAlphabet {
+                    symbol: symbol.clone(),
+                    location,
+                });
             }
+        }
+    }
 
-            if !self.states.contains(&transition.next_state) {
-                return Err(SepError::TransitionStateNotInStates);
+    /// Adiciona em `errors` os erros das transições por classe, na ordem do arquivo de
+    /// origem, incluindo as ambiguidades entre classes de mesma precedência caso
+    /// `ambiguity` seja `true`.
+    fn class_errors(&self, ambiguity: bool, errors: &mut Vec<SepError>) {
+        let mut states: Vec<_> = self.class_map.iter().collect();
+        states.sort_by_key(|(state, classes)| {
+            let index = classes.iter().filter_map(|class| class.index).min();
+            (index.is_none(), index, *state)
+        });
+
+        for (state, classes) in states {
+            for (i, class) in classes.iter().enumerate() {
+                let location = self.location(class.index);
+                if !self.states.contains(state) {
+                    errors.push(SepError::TransitionStateNotInStates {
+                        state: state.clone(),
+                        location,
+                    });
+                }
+                for symbol in class.class.symbols() {
+                    if !self.alphabet.contains(symbol) {
+                        errors.push(SepError::TransitionSymbolNotInAlphabet {
+                            symbol: symbol.clone(),
+                            location,
+                        });
+                    }
+                }
+                self.action_errors(&class.transition, location, errors);
+
+                if !ambiguity {
+                    continue;
+                }
+                for previous in &classes[..i] {
+                    if previous.class.precedence() != class.class.precedence()
+                        || previous.transition == class.transition
+                    {
+                        continue;
+                    }
+                    let ambiguous = self.alphabet.iter().find(|&symbol| {
+                        let key = (state, symbol);
+                        previous.class.matches(symbol)
+                            && class.class.matches(symbol)
+                            && !self.transition_map.contains_key(&key as &dyn TransitionKey)
+                    });
+                    if let Some(symbol) = ambiguous {
+                        errors.push(SepError::AmbiguousTransitions {
+                            state: state.clone(),
+                            symbol: symbol.clone(),
+                            first: self.location(previous.index),
+                            second: location,
+                        });
+                    }
+                }
             }
-            if !self.alphabet.contains(&transition.write_symbol) {
-                return Err(SepError::TransitionSymbolNotInAlphabet);
+        }
+    }
+
+    /// Retorna a localização no arquivo de origem da transição de índice `index`: sua
+    /// linha, caso o formato de origem seja organizado em linhas, ou o próprio índice.
+    fn location(&self, index: Option<usize>) -> Option<Location> {
+        Some(Location::of(index?, &self.transition_lines))
+    }
+
+    /// Retorna o índice no arquivo de origem da transição `transition`, que parte de
+    /// `state` lendo `read`.
+    fn declaration_index(
+        &self,
+        state: &String,
+        read: &Read,
+        transition: &Transition,
+    ) -> Option<usize> {
+        match read {
+            Read::Symbol(symbol) => self
+                .transition_indices
+                .get(&(state, symbol) as &dyn TransitionKey)
+                .copied(),
+            Read::Class(read) => {
+                self.class_map
+                    .get(state)?
+                    .iter()
+                    .find(|class| class.class == *read && ptr::eq(&class.transition, transition))?
+                    .index
             }
         }
+    }
+}
 
-        Ok(())
+/// Serializa a sétupla no mesmo esquema aceito por [`Septuple::from_json`], permitindo
+/// sua conversão para qualquer formato suportado pelo `serde`.
+impl Serialize for Septuple {
+    fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        JsonSeptuple::from_septuple(self).serialize(serializer)
     }
 }
 
-/// Define os erros que podem ocorrer durante a validação de uma [`Septuple`].
-#[derive(Debug)]
+/// Define os erros que podem ocorrer durante a validação de uma [`Septuple`]. Erros
+/// relativos a transições possuem o índice da transição no arquivo de origem, caso
+/// exista.
+#[derive(Debug, Clone, PartialEq, Eq)]
 pub enum SepError {
     /// O símbolo branco não está no alfabeto.
-    BlankNotInAlph,
-    /// O conjunto de _input symbols_ não é um subconjunto do alfabeto.
-    InputNotSubAlph,
+    BlankNotInAlph(Symbol),
+    /// Um símbolo do conjunto de _input symbols_ não está no alfabeto.
+    InputNotSubAlph(Symbol),
     /// Estado inicial não está no conjunto de estados.
-    InitNotInStates,
-    /// O conjunto de estados finais não é um subconjunto dos estados.
-    FinalNotSubStates,
+    InitNotInStates(String),
+    /// Um estado final não está no conjunto de estados.
+    FinalNotSubStates(String),
+    /// Um estado de rejeição não está no conjunto de estados.
+    RejectNotSubStates(String),
+    /// Um estado de parada não está no conjunto de estados.
+    HaltNotSubStates(String),
+    /// Um estado pertence a mais de um dos conjuntos de estados finais, de rejeição e de
+    /// parada.
+    OverlappingHaltStates(String),
     /// Um estado definido nas transições não está no conjunto de estados.
-    TransitionStateNotInStates,
+    TransitionStateNotInStates {
+        state: String,
+        location: Option<Location>,
+    },
     /// Um símbolo definido nas transições não está no alfabeto.
-    TransitionSymbolNotInAlphabet,
+    TransitionSymbolNotInAlphabet {
+        symbol: Symbol,
+        location: Option<Location>,
+    },
+    /// Duas transições por classe de mesma precedência, partindo do mesmo estado, se
+    /// aplicam ao mesmo símbolo e definem ações diferentes.
+    AmbiguousTransitions {
+        state: String,
+        symbol: Symbol,
+        first: Option<Location>,
+        second: Option<Location>,
+    },
+}
+
+/// A localização de uma transição no arquivo de origem.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Location {
+    /// O índice da transição, na ordem de declaração.
+    Index(usize),
+    /// A linha da transição, iniciada em 1.
+    Line(usize),
+}
+
+impl Location {
+    /// Retorna a localização da transição de índice `index`: sua linha, caso `lines`
+    /// possua as linhas das transições, ou o próprio índice.
+    fn of(index: usize, lines: &[usize]) -> Location {
+        match lines.get(index) {
+            Some(&line) => Location::Line(line),
+            None => Location::Index(index),
+        }
+    }
+}
+
+impl Display for Location {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Location::Index(index) => write!(f, "transição de índice {index}"),
+            Location::Line(line) => write!(f, "transição da linha {line}"),
+        }
+    }
 }
 
 impl Display for SepError {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         match self {
-            SepError::BlankNotInAlph => write!(f, "símbolo branco não está contido no alfabeto"),
-            SepError::InputNotSubAlph => {
-                let msg = "alfabeto de símbolos não é subconjunto do alfabeto da fita";
-                write!(f, "{msg}")
+            SepError::BlankNotInAlph(symbol) => {
+                write!(f, "símbolo branco '{symbol}' não está contido no alfabeto")
+            }
+            SepError::InputNotSubAlph(symbol) => {
+                let msg = "não está contido no alfabeto da fita";
+                write!(f, "símbolo de entrada '{symbol}' {msg}")
+            }
+            SepError::InitNotInStates(state) => {
+                write!(
+                    f,
+                    "estado inicial \"{state}\" não está contido no conjunto de estados"
+                )
+            }
+            SepError::FinalNotSubStates(state) => {
+                write!(
+                    f,
+                    "estado final \"{state}\" não está contido no conjunto de estados"
+                )
+            }
+            SepError::RejectNotSubStates(state) => {
+                let msg = "não está contido no conjunto de estados";
+                write!(f, "estado de rejeição \"{state}\" {msg}")
+            }
+            SepError::HaltNotSubStates(state) => {
+                let msg = "não está contido no conjunto de estados";
+                write!(f, "estado de parada \"{state}\" {msg}")
+            }
+            SepError::OverlappingHaltStates(state) => {
+                let msg =
+                    "pertence a mais de um conjunto de estados finais, de rejeição ou de parada";
+                write!(f, "estado \"{state}\" {msg}")
+            }
+            SepError::TransitionStateNotInStates { state, location } => {
+                let location = transition_location(*location);
+                let msg = "não está contido no conjunto de estados";
+                write!(f, "{location}: estado \"{state}\" {msg}")
+            }
+            SepError::TransitionSymbolNotInAlphabet { symbol, location } => {
+                let location = transition_location(*location);
+                write!(
+                    f,
+                    "{location}: símbolo '{symbol}' não está contido no alfabeto"
+                )
+            }
+            SepError::AmbiguousTransitions {
+                state,
+                symbol,
+                first,
+                second,
+            } => {
+                let first = transition_location(*first);
+                let second = transition_location(*second);
+                write!(
+                    f,
+                    "{first} e {second} são ambíguas para ({state}, {symbol}): ambas têm a mesma precedência"
+                )
+            }
+        }
+    }
+}
+
+/// Duas transições declaradas para o mesmo estado e símbolo. Caso sejam idênticas,
+/// representa um aviso; caso contrário, um conflito.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TransitionClash {
+    pub state: String,
+    pub symbol: Symbol,
+    /// Localização da primeira declaração, a que é mantida na sétupla.
+    pub first: Location,
+    /// Localização da declaração repetida.
+    pub second: Location,
+}
+
+impl Display for TransitionClash {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let key = format!("({}, {})", self.state, self.symbol);
+        match (self.first, self.second) {
+            (Location::Index(first), Location::Index(second)) => {
+                write!(f, "transições de índices {first} e {second} para {key}")
+            }
+            (Location::Line(first), Location::Line(second)) => {
+                write!(f, "transições das linhas {first} e {second} para {key}")
+            }
+            (first, second) => write!(f, "{first} e {second} para {key}"),
+        }
+    }
+}
+
+/// Define os erros que podem ocorrer durante o carregamento de uma [`Septuple`].
+#[derive(Debug)]
+pub enum LoadError {
+    /// O arquivo não é um JSON válido ou não segue o formato da sétupla.
+    Json(serde_json::Error),
+    /// O arquivo não é um YAML válido ou não segue o formato da sétupla.
+    Yaml(serde_yaml::Error),
+    /// O arquivo não é um TOML válido ou não segue o formato da sétupla.
+    Toml(toml::de::Error),
+    /// Há transições conflitantes, isso é, a máquina seria não determinística.
+    Conflicts(Vec<TransitionClash>),
+    /// O arquivo não é uma máquina de Turing do JFLAP suportada.
+    Jflap(JflapError),
+    /// O arquivo não segue o formato textual.
+    Text(TextError),
+    /// O arquivo não segue o formato do simulador de Morphett.
+    Morphett(MorphettError),
+    /// O arquivo não segue o formato do turingmachine.io.
+    Tmio(TmioError),
+    /// O formato não pode ser importado, apenas exportado.
+    Unsupported(Format),
+}
+
+impl Display for LoadError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            LoadError::Json(err) => write!(f, "{err}"),
+            LoadError::Yaml(err) => write!(f, "{err}"),
+            LoadError::Toml(err) => write!(f, "{err}"),
+            LoadError::Jflap(err) => write!(f, "{err}"),
+            LoadError::Text(err) => write!(f, "{err}"),
+            LoadError::Morphett(err) => write!(f, "{err}"),
+            LoadError::Tmio(err) => write!(f, "{err}"),
+            LoadError::Unsupported(format) => {
+                write!(f, "sétuplas não podem ser carregadas do formato {format}")
             }
-            SepError::InitNotInStates => {
-                write!(f, "estado inicial não está contido no conjunto de estados")
+            LoadError::Conflicts(conflicts) => {
+                let conflicts: Vec<_> = conflicts.iter().map(|c| c.to_string()).collect();
+                write!(f, "conflitos entre {}", conflicts.join("; "))
             }
-            SepError::FinalNotSubStates => {
-                let msg = "conjunto de estados finais não é um subconjunto do conjunto de estados";
-                write!(f, "{msg}")
+        }
+    }
+}
+
+impl std::error::Error for LoadError {}
+
+/// Separa as transições declaradas em transições por classe, agrupadas por estado, e
+/// transições exatas, cada uma com seu índice no arquivo de origem. Transições por classe
+/// declaradas mais de uma vez de forma idêntica são consideradas apenas uma vez.
+fn split_declarations(
+    declarations: Vec<DeclaredTransition>,
+) -> (ClassMap, Vec<(usize, KeyedTransition)>) {
+    let mut class_map = ClassMap::new();
+    let mut transitions = vec![];
+    for (index, ((state, read), transition)) in declarations.into_iter().enumerate() {
+        match read {
+            Read::Symbol(symbol) => transitions.push((index, ((state, symbol), transition))),
+            Read::Class(class) => {
+                let classes = class_map.entry(state).or_default();
+                let repeated = classes
+                    .iter()
+                    .any(|existing| existing.class == class && existing.transition == transition);
+                if !repeated {
+                    classes.push(ClassTransition {
+                        class,
+                        transition,
+                        index: Some(index),
+                    });
+                }
             }
-            SepError::TransitionStateNotInStates => {
-                let msg = "um estado de transição não está contido no conjunto de estados";
-                write!(f, "{msg}")
+        }
+    }
+    (class_map, transitions)
+}
+
+/// Constrói o mapa de transições e os índices de cada transição à partir de transições
+/// na ordem em que foram declaradas, cada uma com seu índice. Retorna também as
+/// declarações duplicadas, localizadas pelas linhas `lines` caso existam.
+///
+/// # Erros
+/// Retorna todos os pares de transições conflitantes.
+fn build_transition_map(
+    transitions: impl IntoIterator<Item = (usize, KeyedTransition)>,
+    lines: &[usize],
+) -> Result<(TransitionMap, TransitionIndices, Vec<TransitionClash>), LoadError> {
+    let mut transition_map = TransitionMap::new();
+    let mut transition_indices = TransitionIndices::new();
+    let mut duplicates = vec![];
+    let mut conflicts = vec![];
+
+    for (index, (key, transition)) in transitions {
+        match transition_map.get(&key) {
+            Some(existing) => {
+                let clash = TransitionClash {
+                    state: key.0.clone(),
+                    symbol: key.1.clone(),
+                    first: Location::of(transition_indices[&key], lines),
+                    second: Location::of(index, lines),
+                };
+                if *existing == transition {
+                    duplicates.push(clash);
+                } else {
+                    conflicts.push(clash);
+                }
             }
-            SepError::TransitionSymbolNotInAlphabet => {
-                write!(f, "um símbolo de transição não está contido no alfabeto")
+            None => {
+                transition_indices.insert(key.clone(), index);
+                transition_map.insert(key, transition);
             }
         }
     }
+
+    if conflicts.is_empty() {
+        Ok((transition_map, transition_indices, duplicates))
+    } else {
+        Err(LoadError::Conflicts(conflicts))
+    }
+}
+
+/// Descreve a localização de uma transição no arquivo de origem.
+fn transition_location(location: Option<Location>) -> String {
+    match location {
+        Some(location) => location.to_string(),
+        None => "transição".to_string(),
+    }
 }
 
 impl std::error::Error for SepError {}
 
 /// Define os movimentos que podem ser tomados em transição.
-#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, Deserialize)]
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, Deserialize, Serialize)]
 pub enum Movement {
     /// Mover para a direita.
     R,
@@ -135,32 +870,235 @@ pub enum Movement {
     L,
 }
 
+impl Movement {
+    /// Retorna a letra do movimento `movement` (`R` ou `L`), ou `stay` caso a cabeça
+    /// não se mova.
+    pub fn symbol(movement: Option<Movement>, stay: &str) -> &str {
+        match movement {
+            Some(Movement::R) => "R",
+            Some(Movement::L) => "L",
+            None => stay,
+        }
+    }
+}
+
 /// Define as ações a serem tomadas na aplicação de uma transição.
 #[derive(Clone, Debug, PartialEq, Eq, Hash)]
 pub struct Transition {
-    pub write_symbol: char,
+    /// O símbolo escrito na fita. Caso [`None`], o símbolo lido é mantido.
+    pub write_symbol: Option<Symbol>,
     pub next_state: String,
     pub move_to: Option<Movement>,
 }
 
+impl Transition {
+    /// Retorna o símbolo escrito pela transição ao ler `read_symbol`.
+    pub fn written(&self, read_symbol: &Symbol) -> Symbol {
+        self.write_symbol.as_ref().unwrap_or(read_symbol).clone()
+    }
+}
+
+/// Uma classe de símbolos lidos por uma transição, que se aplica a todos os símbolos do
+/// alfabeto que ela contém.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum SymbolClass {
+    /// Qualquer símbolo.
+    Any,
+    /// Qualquer símbolo, exceto os listados.
+    Except(IndexSet<Symbol>),
+    /// Apenas os símbolos listados.
+    In(IndexSet<Symbol>),
+}
+
+impl SymbolClass {
+    /// Retorna `true` caso `symbol` pertença à classe.
+    pub fn matches(&self, symbol: &Symbol) -> bool {
+        match self {
+            SymbolClass::Any => true,
+            SymbolClass::Except(symbols) => !symbols.contains(symbol),
+            SymbolClass::In(symbols) => symbols.contains(symbol),
+        }
+    }
+
+    /// A precedência da classe na busca de transições. Quanto mais específica a classe,
+    /// maior a precedência.
+    pub fn precedence(&self) -> u8 {
+        match self {
+            SymbolClass::Any => 0,
+            SymbolClass::Except(_) => 1,
+            SymbolClass::In(_) => 2,
+        }
+    }
+
+    /// Os símbolos listados na definição da classe.
+    pub fn symbols(&self) -> impl Iterator<Item = &Symbol> {
+        match self {
+            SymbolClass::Any => None,
+            SymbolClass::Except(symbols) | SymbolClass::In(symbols) => Some(symbols),
+        }
+        .into_iter()
+        .flatten()
+    }
+}
+
+/// Serializa a classe como `"any"`, `{"except": [...]}` ou `{"in": [...]}`, com a mesma
+/// forma em todos os formatos. A serialização derivada usaria tags no YAML.
+impl Serialize for SymbolClass {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        let (name, symbols) = match self {
+            SymbolClass::Any => return serializer.serialize_str("any"),
+            SymbolClass::Except(symbols) => ("except", symbols),
+            SymbolClass::In(symbols) => ("in", symbols),
+        };
+        let mut map = serializer.serialize_map(Some(1))?;
+        map.serialize_entry(name, symbols)?;
+        map.end()
+    }
+}
+
+impl Display for SymbolClass {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let list = |symbols: &IndexSet<Symbol>| {
+            let symbols: Vec<_> = symbols.iter().map(Symbol::as_str).collect();
+            symbols.join(", ")
+        };
+        match self {
+            SymbolClass::Any => write!(f, "*"),
+            SymbolClass::Except(symbols) => write!(f, "¬{{{}}}", list(symbols)),
+            SymbolClass::In(symbols) => write!(f, "{{{}}}", list(symbols)),
+        }
+    }
+}
+
+/// Uma transição aplicada a uma classe de símbolos.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct ClassTransition {
+    pub class: SymbolClass,
+    pub transition: Transition,
+    /// O índice da transição no arquivo de origem, caso exista.
+    pub index: Option<usize>,
+}
+
+/// O que uma transição lê: um único símbolo ou uma classe de símbolos.
+///
+/// É serializado como o próprio símbolo ou como a classe: `"any"`, `{"except": [...]}`
+/// ou `{"in": [...]}`. Um símbolo chamado `any` é escrito como `{"symbol": "any"}`.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum Read {
+    Symbol(Symbol),
+    Class(SymbolClass),
+}
+
+/// O nome que representa [`SymbolClass::Any`] no campo `read_symbol`.
+const ANY: &str = "any";
+
+impl Display for Read {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Read::Symbol(symbol) => write!(f, "{symbol}"),
+            Read::Class(class) => write!(f, "{class}"),
+        }
+    }
+}
+
+impl Serialize for Read {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        match self {
+            Read::Symbol(symbol) if symbol.as_str() == ANY => {
+                let mut map = serializer.serialize_map(Some(1))?;
+                map.serialize_entry("symbol", symbol)?;
+                map.end()
+            }
+            Read::Symbol(symbol) => symbol.serialize(serializer),
+            Read::Class(class) => class.serialize(serializer),
+        }
+    }
+}
+
+impl<'de> Deserialize<'de> for Read {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        deserializer.deserialize_any(ReadVisitor)
+    }
+}
+
+/// Lê um [`Read`]. A implementação é manual para aceitar símbolos escritos como números
+/// em YAML, como `0`, que não seriam aceitos por um enum `untagged`.
+struct ReadVisitor;
+
+impl<'de> de::Visitor<'de> for ReadVisitor {
+    type Value = Read;
+
+    fn expecting(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        write!(f, "um símbolo ou uma classe de símbolos")
+    }
+
+    fn visit_char<E: de::Error>(self, symbol: char) -> Result<Read, E> {
+        Ok(Read::Symbol(symbol.into()))
+    }
+
+    fn visit_str<E: de::Error>(self, name: &str) -> Result<Read, E> {
+        if name == ANY {
+            return Ok(Read::Class(SymbolClass::Any));
+        }
+        Symbol::deserialize(name.into_deserializer()).map(Read::Symbol)
+    }
+
+    fn visit_u64<E: de::Error>(self, number: u64) -> Result<Read, E> {
+        self.visit_str(&number.to_string())
+    }
+
+    fn visit_i64<E: de::Error>(self, number: i64) -> Result<Read, E> {
+        self.visit_str(&number.to_string())
+    }
+
+    fn visit_map<A: de::MapAccess<'de>>(self, mut map: A) -> Result<Read, A::Error> {
+        const FIELDS: &[&str] = &["symbol", "except", "in"];
+        let Some(key) = map.next_key::<String>()? else {
+            return Err(de::Error::invalid_length(0, &self));
+        };
+        let read = match key.as_str() {
+            "symbol" => Read::Symbol(map.next_value()?),
+            "except" => Read::Class(SymbolClass::Except(map.next_value()?)),
+            "in" => Read::Class(SymbolClass::In(map.next_value()?)),
+            _ => return Err(de::Error::unknown_field(&key, FIELDS)),
+        };
+        if map.next_key::<String>()?.is_some() {
+            return Err(de::Error::invalid_length(2, &self));
+        }
+        Ok(read)
+    }
+}
+
 mod json {
     //! Módulo da representação em JSON da sétupla de definição da Máquina de Turing.
 
-    use super::{Movement, Septuple};
-    use serde::Deserialize;
-    use std::collections::HashSet;
+    use super::{DeclaredTransition, LoadError, Movement, Read, Septuple, Symbol, TransitionClash};
+    use indexmap::IndexSet;
+    use serde::{Deserialize, Serialize};
 
     /// Uma estrutura similar à [`Septuple`](super::Septuple), porém editada para
     /// permitir a representação em JSON.
-    #[derive(Deserialize)]
+    #[derive(Deserialize, Serialize)]
     pub struct JsonSeptuple {
-        alphabet: HashSet<char>,
-        blank_symbol: char,
-        input_symbols: HashSet<char>,
-        states: HashSet<String>,
+        alphabet: IndexSet<Symbol>,
+        blank_symbol: Symbol,
+        input_symbols: IndexSet<Symbol>,
+        states: IndexSet<String>,
         initial_state: String,
-        final_states: HashSet<String>,
-        transitions: HashSet<Transition>,
+        final_states: IndexSet<String>,
+        #[serde(default, skip_serializing_if = "IndexSet::is_empty")]
+        reject_states: IndexSet<String>,
+        #[serde(default, skip_serializing_if = "IndexSet::is_empty")]
+        halt_states: IndexSet<String>,
+        // declarado antes de `transitions`, já que no TOML valores simples devem preceder
+        // as tabelas
+        #[serde(default, skip_serializing_if = "is_false")]
+        two_way_tape: bool,
+        transitions: Vec<Transition>,
+    }
+
+    fn is_false(value: &bool) -> bool {
+        !value
     }
 
     /// Uma estrutura similar à [`Transition`](super::Transition), porém definida com o
@@ -169,56 +1107,108 @@ mod json {
     /// Essa estrutura é necessária para permitir que a tabela de transições seja mapeada
     /// em um JSON. Na sétupla original, o mapa de transições possue uma tupla como sua
     /// chave, porém o JSON só aceita chaves que sejam strings.
-    #[derive(Deserialize, Hash, PartialEq, Eq)]
+    ///
+    /// O símbolo lido pode ser uma classe de símbolos, e o símbolo escrito pode ser
+    /// omitido para manter o símbolo lido.
+    #[derive(Deserialize, Serialize)]
     struct Transition {
         from_state: String,
-        read_symbol: char,
+        read_symbol: Read,
 
-        write_symbol: char,
-        next_state: String,
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        write_symbol: Option<Symbol>,
         move_to: Option<Movement>,
+        next_state: String,
     }
 
-    impl From<JsonSeptuple> for Septuple {
-        fn from(json: JsonSeptuple) -> Self {
-            let mut transition_map = super::TransitionMap::new();
-            for transition in json.transitions {
-                transition_map.insert(
-                    (transition.from_state, transition.read_symbol),
-                    super::Transition {
-                        write_symbol: transition.write_symbol,
-                        next_state: transition.next_state,
-                        move_to: transition.move_to,
-                    },
-                );
-            }
+    impl JsonSeptuple {
+        /// Constrói a representação em JSON de uma [`Septuple`], com as transições na
+        /// ordem de [`Septuple::declared_transitions`].
+        pub fn from_septuple(septuple: &Septuple) -> JsonSeptuple {
+            let transitions = septuple
+                .declared_transitions()
+                .into_iter()
+                .map(|(from_state, read_symbol, transition)| Transition {
+                    from_state: from_state.clone(),
+                    read_symbol,
+                    write_symbol: transition.write_symbol.clone(),
+                    move_to: transition.move_to,
+                    next_state: transition.next_state.clone(),
+                })
+                .collect();
 
-            Septuple {
-                alphabet: json.alphabet,
-                blank_symbol: json.blank_symbol,
-                input_symbols: json.input_symbols,
-                states: json.states,
-                initial_state: json.initial_state,
-                final_states: json.final_states,
-                transition_map,
+            JsonSeptuple {
+                alphabet: septuple.alphabet.clone(),
+                blank_symbol: septuple.blank_symbol.clone(),
+                input_symbols: septuple.input_symbols.clone(),
+                states: septuple.states.clone(),
+                initial_state: septuple.initial_state.clone(),
+                final_states: septuple.final_states.clone(),
+                reject_states: septuple.reject_states.clone(),
+                halt_states: septuple.halt_states.clone(),
+                two_way_tape: septuple.two_way_tape,
+                transitions,
             }
         }
+
+        /// Converte a representação em JSON para uma [`Septuple`], retornando também as
+        /// transições duplicadas.
+        pub fn into_septuple(self) -> Result<(Septuple, Vec<TransitionClash>), LoadError> {
+            let (septuple, transitions) = self.into_parts();
+            septuple.with_declarations(transitions)
+        }
+
+        /// Separa a representação em JSON em uma [`Septuple`] sem transições e as
+        /// transições declaradas, na ordem do arquivo.
+        pub fn into_parts(self) -> (Septuple, Vec<DeclaredTransition>) {
+            let transitions = self
+                .transitions
+                .into_iter()
+                .map(|transition| {
+                    (
+                        (transition.from_state, transition.read_symbol),
+                        super::Transition {
+                            write_symbol: transition.write_symbol,
+                            next_state: transition.next_state,
+                            move_to: transition.move_to,
+                        },
+                    )
+                })
+                .collect();
+
+            let septuple = Septuple {
+                final_states: self.final_states,
+                reject_states: self.reject_states,
+                halt_states: self.halt_states,
+                two_way_tape: self.two_way_tape,
+                ..Septuple::new(
+                    self.alphabet,
+                    self.blank_symbol,
+                    self.input_symbols,
+                    self.states,
+                    self.initial_state,
+                )
+            };
+            (septuple, transitions)
+        }
     }
 }
 
 mod transition_key {
-    //! Esse módulo exporta a trait `TransitionKey`, implementada em `(String, char)`
-    //! e `(&String, &char)`. A implementação é baseada em:
+    //! Esse módulo exporta a trait `TransitionKey`, implementada em `(String, Symbol)`
+    //! e `(&String, &Symbol)`. A implementação é baseada em:
     //! <https://stackoverflow.com/a/45795699/13310655>
 
     use std::borrow::Borrow;
     use std::hash::Hash;
 
-    /// As implementações dessa trait permitem o uso de uma tupla `(&String, &char)` para
-    /// um get em `HashMap<(String, char), _>`.
+    use super::Symbol;
+
+    /// As implementações dessa trait permitem o uso de uma tupla `(&String, &Symbol)`
+    /// para um get em `HashMap<(String, Symbol), _>`.
     ///
     /// Na ausência dessa implementação, todo `get` em um mapa do tipo, iria requerer uma
-    /// tupla `&(String, char)`. A tupla teria *ownership* da string, dificultando o uso
+    /// tupla `&(String, Symbol)`. A tupla teria *ownership* da string, dificultando o uso
     /// de referências de strings para chamadas de `get`.
     ///
     /// A implementação é usada diretamente em [`Machine`](crate::machine::Machine), onde
@@ -226,35 +1216,35 @@ mod transition_key {
     /// dessa referência, é possível obter o estado atual (uma `&String`). A
     /// implementação é usada para permitir o uso desse estado atual na busca no mapa de
     /// transições sem a necessidade de um `.clone()` (algo que seria necessário para
-    /// passar uma tupla `&(String, char)`).
+    /// passar uma tupla `&(String, Symbol)`).
     pub trait TransitionKey {
         // ao retornar os próprios dados, é possível implementar `Eq` e `Hash` na
         // trait.
         fn string(&self) -> &String;
-        fn char(&self) -> &char;
+        fn symbol(&self) -> &Symbol;
     }
 
-    impl TransitionKey for (String, char) {
+    impl TransitionKey for (String, Symbol) {
         fn string(&self) -> &String {
             &self.0
         }
 
-        fn char(&self) -> &char {
+        fn symbol(&self) -> &Symbol {
             &self.1
         }
     }
 
-    impl TransitionKey for (&String, &char) {
+    impl TransitionKey for (&String, &Symbol) {
         fn string(&self) -> &String {
             self.0
         }
 
-        fn char(&self) -> &char {
+        fn symbol(&self) -> &Symbol {
             self.1
         }
     }
 
-    impl<'a> Borrow<dyn TransitionKey + 'a> for (String, char) {
+    impl<'a> Borrow<dyn TransitionKey + 'a> for (String, Symbol) {
         fn borrow(&self) -> &(dyn TransitionKey + 'a) {
             self
         }
@@ -263,13 +1253,13 @@ mod transition_key {
     impl Hash for dyn TransitionKey + '_ {
         fn hash<H: std::hash::Hasher>(&self, state: &mut H) {
             self.string().hash(state);
-            self.char().hash(state);
+            self.symbol().hash(state);
         }
     }
 
     impl PartialEq for dyn TransitionKey + '_ {
         fn eq(&self, other: &Self) -> bool {
-            self.string() == other.string() && self.char() == other.char()
+            self.string() == other.string() && self.symbol() == other.symbol()
         }
     }
 
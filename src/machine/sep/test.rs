@@ -0,0 +1,137 @@
+use super::*;
+
+/// Define, em JSON, uma sétupla com diversos erros de definição. Os estados finais não
+/// declarados estão fora de ordem crescente.
+static INVALID_JSON: &str = r#"{"alphabet":["0","1"],"blank_symbol":"B","input_symbols":["0","2"],"states":["q0","q1"],"initial_state":"q9","final_states":["q1","q5","q4"],"reject_states":["q0","q6"],"halt_states":["q0"],"transitions":[{"from_state":"q0","read_symbol":"0","write_symbol":"0","move_to":"R","next_state":"q1"},{"from_state":"q0","read_symbol":"1","write_symbol":"X","move_to":"R","next_state":"q7"},{"from_state":"q8","read_symbol":"0","write_symbol":"0","move_to":"L","next_state":"q0"}]}"#;
+
+#[test]
+fn test_all_errors() {
+    let septuple = Septuple::from_json(INVALID_JSON).unwrap();
+    let errors = septuple.valid().unwrap_err();
+
+    assert_eq!(
+        errors,
+        vec![
+            SepError::BlankNotInAlph('B'.into()),
+            SepError::InputNotSubAlph('2'.into()),
+            SepError::InitNotInStates("q9".to_string()),
+            SepError::FinalNotSubStates("q5".to_string()),
+            SepError::FinalNotSubStates("q4".to_string()),
+            SepError::RejectNotSubStates("q6".to_string()),
+            SepError::OverlappingHaltStates("q0".to_string()),
+            SepError::TransitionStateNotInStates {
+                state: "q7".to_string(),
+                location: Some(Location::Index(1)),
+            },
+            SepError::TransitionSymbolNotInAlphabet {
+                symbol: 'X'.into(),
+                location: Some(Location::Index(1)),
+            },
+            SepError::TransitionStateNotInStates {
+                state: "q8".to_string(),
+                location: Some(Location::Index(2)),
+            },
+        ]
+    );
+}
+
+/// Define, em JSON, uma sétupla com uma transição duplicada (índices 0 e 2) e uma
+/// transição conflitante (índices 1 e 3).
+static CLASHING_JSON: &str = r#"{"alphabet":["0","1","B"],"blank_symbol":"B","input_symbols":["0","1"],"states":["q0","q1"],"initial_state":"q0","final_states":["q1"],"transitions":[{"from_state":"q0","read_symbol":"0","write_symbol":"0","move_to":"R","next_state":"q0"},{"from_state":"q0","read_symbol":"1","write_symbol":"1","move_to":"R","next_state":"q1"},{"from_state":"q0","read_symbol":"0","write_symbol":"0","move_to":"R","next_state":"q0"},{"from_state":"q0","read_symbol":"1","write_symbol":"0","move_to":"L","next_state":"q1"}]}"#;
+
+#[test]
+fn test_duplicates_and_conflicts() {
+    let conflicts = match Septuple::from_json(CLASHING_JSON) {
+        Err(LoadError::Conflicts(conflicts)) => conflicts,
+        other => panic!("expected conflicts, got {other:?}"),
+    };
+    assert_eq!(
+        conflicts,
+        vec![TransitionClash {
+            state: "q0".to_string(),
+            symbol: '1'.into(),
+            first: Location::Index(1),
+            second: Location::Index(3),
+        }]
+    );
+
+    // sem o conflito, resta apenas o aviso da transição duplicada
+    let json = CLASHING_JSON.replace(
+        r#",{"from_state":"q0","read_symbol":"1","write_symbol":"0","move_to":"L","next_state":"q1"}"#,
+        "",
+    );
+    let (septuple, duplicates) = Septuple::from_json_with_warnings(&json).unwrap();
+    assert_eq!(septuple.transition_map.len(), 2);
+    assert_eq!(
+        duplicates,
+        vec![TransitionClash {
+            state: "q0".to_string(),
+            symbol: '0'.into(),
+            first: Location::Index(0),
+            second: Location::Index(2),
+        }]
+    );
+}
+
+/// Define, em JSON, uma sétupla cujos estados e símbolos não são declarados em ordem
+/// crescente.
+static UNSORTED_JSON: &str = r#"{"alphabet":["b","a","B"],"blank_symbol":"B","input_symbols":["b","a"],"states":["q1","q0"],"initial_state":"q1","final_states":["q0"],"transitions":[{"from_state":"q0","read_symbol":"a","write_symbol":"a","move_to":"R","next_state":"q0"},{"from_state":"q1","read_symbol":"a","write_symbol":"a","move_to":"R","next_state":"q0"},{"from_state":"q1","read_symbol":"b","write_symbol":"b","move_to":"R","next_state":"q1"},{"from_state":"q2","read_symbol":"a","write_symbol":"a","move_to":"R","next_state":"q1"}]}"#;
+
+#[test]
+fn test_declaration_order() {
+    let septuple = Septuple::from_json(UNSORTED_JSON).unwrap();
+    assert_eq!(format!("{:?}", septuple.alphabet), "{'b', 'a', 'B'}");
+    assert_eq!(format!("{:?}", septuple.states), r#"{"q1", "q0"}"#);
+
+    // q2 não foi declarado e vem por último
+    let keys: Vec<_> = septuple
+        .sorted_transitions()
+        .into_iter()
+        .map(|(state, symbol, _)| format!("{state}{symbol}"))
+        .collect();
+    assert_eq!(keys, vec!["q1b", "q1a", "q0a", "q2a"]);
+}
+
+#[test]
+fn test_json_round_trip() {
+    let septuple = Septuple::from_json(UNSORTED_JSON).unwrap();
+    let json = septuple.to_json();
+    let reloaded = Septuple::from_json(&json).unwrap();
+
+    assert_eq!(reloaded.alphabet, septuple.alphabet);
+    assert_eq!(reloaded.states, septuple.states);
+    assert_eq!(reloaded.transition_map, septuple.transition_map);
+    assert_eq!(reloaded.transition_indices, septuple.transition_indices);
+    assert_eq!(reloaded.to_json(), json);
+
+    // campos opcionais são omitidos quando assumem o valor padrão
+    assert!(!json.contains("reject_states"));
+    assert!(!json.contains("two_way_tape"));
+    assert!(json.starts_with("{\n  \"alphabet\": [\n    \"b\",\n    \"a\","));
+}
+
+#[test]
+fn test_yaml_toml_round_trip() {
+    let mut septuple = Septuple::from_json(UNSORTED_JSON).unwrap();
+    septuple.two_way_tape = true;
+    septuple
+        .transition_map
+        .get_mut(&("q0".to_string(), 'a'.into()))
+        .unwrap()
+        .move_to = None;
+
+    let yaml = septuple.to_yaml();
+    let (from_yaml, _) = Septuple::from_yaml_with_warnings(&yaml).unwrap();
+    let toml = septuple.to_toml();
+    let (from_toml, _) = Septuple::from_toml_with_warnings(&toml).unwrap();
+
+    for reloaded in [from_yaml, from_toml] {
+        assert_eq!(reloaded.alphabet, septuple.alphabet);
+        assert_eq!(reloaded.states, septuple.states);
+        assert_eq!(reloaded.transition_map, septuple.transition_map);
+        assert_eq!(reloaded.transition_indices, septuple.transition_indices);
+        assert!(reloaded.two_way_tape);
+    }
+    assert!(yaml.starts_with("alphabet:\n- 'b'\n- 'a'\n"));
+    assert!(toml.contains("[[transitions]]"));
+}
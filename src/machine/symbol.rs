@@ -0,0 +1,165 @@
+//! Símbolos da fita. Um símbolo é identificado por um nome, que pode ter mais de um
+//! caractere, como `X1`, `#0` ou `blank`. Máquinas cujos símbolos têm um único
+//! caractere continuam sendo lidas e exibidas como antes.
+
+use std::{
+    borrow::Borrow,
+    fmt::{Debug, Display},
+    rc::Rc,
+};
+
+use indexmap::IndexSet;
+use serde::{de, Deserialize, Deserializer, Serialize, Serializer};
+
+/// Separador aceito entre os símbolos de uma cadeia de entrada, além de espaços em
+/// branco.
+pub const DELIMITER: char = ',';
+
+/// Um símbolo da fita, identificado pelo seu nome. A cópia de um símbolo não copia seu
+/// nome.
+#[derive(Clone, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct Symbol(Rc<str>);
+
+impl Symbol {
+    pub fn as_str(&self) -> &str {
+        &self.0
+    }
+
+    /// Retorna o caractere do símbolo, caso seu nome tenha um único caractere.
+    pub fn as_char(&self) -> Option<char> {
+        let mut chars = self.0.chars();
+        match (chars.next(), chars.next()) {
+            (Some(symbol), None) => Some(symbol),
+            _ => None,
+        }
+    }
+
+    /// Retorna `true` caso o nome do símbolo tenha um único caractere.
+    pub fn is_char(&self) -> bool {
+        self.as_char().is_some()
+    }
+}
+
+impl From<char> for Symbol {
+    fn from(symbol: char) -> Self {
+        Symbol(symbol.to_string().into())
+    }
+}
+
+impl From<&str> for Symbol {
+    fn from(name: &str) -> Self {
+        Symbol(name.into())
+    }
+}
+
+impl From<String> for Symbol {
+    fn from(name: String) -> Self {
+        Symbol(name.into())
+    }
+}
+
+impl Borrow<str> for Symbol {
+    fn borrow(&self) -> &str {
+        &self.0
+    }
+}
+
+impl PartialEq<char> for Symbol {
+    fn eq(&self, other: &char) -> bool {
+        self.as_char() == Some(*other)
+    }
+}
+
+impl Display for Symbol {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.0)
+    }
+}
+
+/// Exibe o símbolo entre aspas simples, como um `char`.
+impl Debug for Symbol {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "'{}'", self.0)
+    }
+}
+
+/// Símbolos de um único caractere são escritos como `char`, como antes da introdução de
+/// símbolos com nomes.
+impl Serialize for Symbol {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        match self.as_char() {
+            Some(symbol) => serializer.serialize_char(symbol),
+            None => serializer.serialize_str(&self.0),
+        }
+    }
+}
+
+impl<'de> Deserialize<'de> for Symbol {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        deserializer.deserialize_any(SymbolVisitor)
+    }
+}
+
+/// Lê um [`Symbol`] escrito como texto ou, em YAML e TOML, como número.
+struct SymbolVisitor;
+
+impl de::Visitor<'_> for SymbolVisitor {
+    type Value = Symbol;
+
+    fn expecting(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        write!(f, "um símbolo")
+    }
+
+    fn visit_char<E: de::Error>(self, symbol: char) -> Result<Symbol, E> {
+        Ok(symbol.into())
+    }
+
+    fn visit_str<E: de::Error>(self, name: &str) -> Result<Symbol, E> {
+        if name.is_empty() {
+            return Err(E::invalid_value(de::Unexpected::Str(name), &self));
+        }
+        Ok(name.into())
+    }
+
+    fn visit_u64<E: de::Error>(self, number: u64) -> Result<Symbol, E> {
+        Ok(number.to_string().into())
+    }
+
+    fn visit_i64<E: de::Error>(self, number: i64) -> Result<Symbol, E> {
+        Ok(number.to_string().into())
+    }
+}
+
+/// Separa uma cadeia de entrada em símbolos.
+///
+/// Caso todos os símbolos de entrada tenham um único caractere e a cadeia não possua
+/// separadores, cada caractere é um símbolo, como em `0011`. Caso contrário, os símbolos
+/// são separados por espaços em branco ou por [`DELIMITER`], como em `X1 X1 #0` ou
+/// `X1,X1,#0`. Caracteres que são símbolos de entrada nunca são separadores.
+pub fn split_input(input: &str, input_symbols: &IndexSet<Symbol>) -> Vec<Symbol> {
+    let separator = |c: char| {
+        (c.is_whitespace() || c == DELIMITER) && !input_symbols.contains(c.to_string().as_str())
+    };
+    let single = input_symbols.iter().all(Symbol::is_char);
+    if single && !input.chars().any(separator) {
+        input.chars().map(Symbol::from).collect()
+    } else {
+        input
+            .split(separator)
+            .filter(|name| !name.is_empty())
+            .map(Symbol::from)
+            .collect()
+    }
+}
+
+/// Escreve uma sequência de símbolos. Caso todos tenham um único caractere, são
+/// concatenados, como em `0011`; caso contrário, são separados por espaços, como em
+/// `X1 X1 #0`.
+pub fn join<'s>(symbols: impl IntoIterator<Item = &'s Symbol>) -> String {
+    let symbols: Vec<_> = symbols.into_iter().map(Symbol::as_str).collect();
+    if symbols.iter().all(|name| name.chars().count() == 1) {
+        symbols.concat()
+    } else {
+        symbols.join(" ")
+    }
+}
@@ -14,7 +14,10 @@ fn test_zeron_onen() {
 
     let mut tm = Machine::new(&septuple, vec!['1', '0']).unwrap();
     while tm.transition().is_none() {}
-    assert_eq!(tm.transition().unwrap(), Acceptance::Rejected);
+    assert_eq!(
+        tm.transition().unwrap(),
+        Acceptance::Rejected(Rejection::NoTransition)
+    );
 
     let mut tm = Machine::new(&septuple, vec!['0', '0', '0', '1', '1', '1']).unwrap();
     while tm.transition().is_none() {}
@@ -29,12 +32,748 @@ fn test_undo() {
 
     // roda a máquina até seu estado de aceitação.
     while tm.transition().is_none() {}
-    assert_eq!(tm.tape, vec!['X', 'X', 'Y', 'Y', 'B', 'B']);
+    assert_eq!(tm.tape(), vec!['X', 'X', 'Y', 'Y', 'B', 'B']);
     assert_eq!(tm.transition().unwrap(), Acceptance::Accepted);
 
     // desfaz as transições da máquina até retornar ao estado inicial
     while tm.undo_transition().is_ok() {}
-    assert_eq!(tm.tape, initial_tape);
+    assert_eq!(tm.tape(), initial_tape);
     assert_eq!(tm.current_state, &tm.septuple.initial_state);
     assert_eq!(tm.current_position, 0);
 }
+
+/// Define uma máquina que alterna entre dois estados movendo a cabeça para a direita e
+/// para a esquerda, sem nunca parar.
+static LOOP_JSON: &str = r#"{"alphabet":["0","B"],"blank_symbol":"B","input_symbols":["0"],"states":["q0","q1"],"initial_state":"q0","final_states":[],"transitions":[{"from_state":"q0","read_symbol":"0","write_symbol":"0","move_to":"R","next_state":"q1"},{"from_state":"q1","read_symbol":"B","write_symbol":"B","move_to":"L","next_state":"q0"}]}"#;
+
+/// Define uma máquina que se move para a direita indefinidamente, sem nunca repetir uma
+/// configuração.
+static RUNAWAY_JSON: &str = r#"{"alphabet":["0","B"],"blank_symbol":"B","input_symbols":["0"],"states":["q0"],"initial_state":"q0","final_states":[],"transitions":[{"from_state":"q0","read_symbol":"0","write_symbol":"0","move_to":"R","next_state":"q0"},{"from_state":"q0","read_symbol":"B","write_symbol":"0","move_to":"R","next_state":"q0"}]}"#;
+
+#[test]
+fn test_loop_detection() {
+    let septuple = Septuple::from_json(LOOP_JSON).unwrap();
+    let mut tm = Machine::new(&septuple, vec!['0']).unwrap();
+    tm.set_loop_detection(true);
+
+    while tm.transition().is_none() {}
+    assert_eq!(tm.acceptance(), Some(Acceptance::Looping));
+    assert_eq!(tm.steps(), 2);
+
+    // ao desfazer, a repetição deixa de existir
+    tm.undo_transition().unwrap();
+    assert_eq!(tm.acceptance(), None);
+    assert_eq!(tm.transition(), None);
+    assert_eq!(tm.transition(), Some(Acceptance::Looping));
+}
+
+#[test]
+fn test_loop_detection_collision() {
+    let septuple = Septuple::from_json(RUNAWAY_JSON).unwrap();
+    let mut tm = Machine::new(&septuple, vec!['0']).unwrap();
+    tm.set_step_limit(Some(10));
+    tm.set_loop_detection(true);
+    assert_eq!(tm.transition(), None);
+
+    // força a configuração atual a colidir com a impressão da configuração inicial, que
+    // é diferente dela
+    let configuration = tm.configuration();
+    let visited = tm.visited.as_mut().unwrap();
+    visited.entry(configuration).or_default().insert(0, 0);
+    assert!(!tm.repeats(0));
+    assert_eq!(tm.acceptance(), None);
+    assert_eq!(tm.run(), Acceptance::Exhausted);
+}
+
+#[test]
+fn test_step_limit() {
+    let septuple = Septuple::from_json(RUNAWAY_JSON).unwrap();
+    let mut tm = Machine::new(&septuple, vec!['0']).unwrap();
+    tm.set_step_limit(Some(50));
+    tm.set_loop_detection(true);
+
+    while tm.transition().is_none() {}
+    assert_eq!(tm.acceptance(), Some(Acceptance::Exhausted));
+    assert_eq!(tm.steps(), 50);
+
+    tm.set_step_limit(None);
+    assert_eq!(tm.transition(), None);
+}
+
+/// Define uma máquina que desloca um número em unário uma posição para a direita,
+/// apagando o primeiro símbolo e escrevendo outro ao final da fita.
+static SHIFT_JSON: &str = r#"{"alphabet":["1","B"],"blank_symbol":"B","input_symbols":["1"],"states":["q0","q1","q2"],"initial_state":"q0","final_states":["q2"],"transitions":[{"from_state":"q0","read_symbol":"1","write_symbol":"B","move_to":"R","next_state":"q1"},{"from_state":"q1","read_symbol":"1","write_symbol":"1","move_to":"R","next_state":"q1"},{"from_state":"q1","read_symbol":"B","write_symbol":"1","move_to":null,"next_state":"q2"}]}"#;
+
+#[test]
+fn test_output() {
+    let septuple = Septuple::from_json(SHIFT_JSON).unwrap();
+
+    let mut tm = Machine::new(&septuple, vec!['1', '1', '1']).unwrap();
+    assert_eq!(tm.run(), Acceptance::Accepted);
+    assert_eq!(tm.tape(), vec!['B', '1', '1', '1']);
+    assert_eq!(tm.output(OutputMode::Trimmed), vec!['1', '1', '1']);
+    assert_eq!(tm.output(OutputMode::FromHead), vec!['1']);
+}
+
+#[test]
+fn test_trace() {
+    let septuple = Septuple::from_json(JSON).unwrap();
+    let mut tm = Machine::new(&septuple, vec!['0', '1']).unwrap();
+
+    let steps: Vec<_> = tm.trace().collect();
+    assert_eq!(steps.len(), 6);
+    assert_eq!(steps[0].transition, None);
+    assert_eq!(steps[5].tape, ['X', 'Y', 'B', 'B']);
+    assert_eq!(steps[5].state, "q3");
+    assert_eq!(tm.acceptance(), Some(Acceptance::Accepted));
+
+    let first = steps[1].transition.as_ref().unwrap();
+    assert_eq!(first.from_state, "q0");
+    assert_eq!(first.read_symbol, '0');
+    assert_eq!(first.write_symbol, 'X');
+    assert_eq!(first.move_to, Some(Movement::R));
+
+    let history: Vec<_> = tm.history().collect();
+    assert_eq!(tm.last_applied().as_ref(), history.last());
+    assert_eq!(tm.history_range(1..3).collect::<Vec<_>>(), history[1..3]);
+    assert_eq!(tm.history_range(4..10).count(), 1);
+
+    let mut csv = vec![];
+    trace::write(trace::Format::Csv, steps, &mut csv).unwrap();
+    let csv = String::from_utf8(csv).unwrap();
+    assert_eq!(csv.lines().count(), 7);
+    assert_eq!(csv.lines().nth(2), Some("1,q1,1,0,X1,q0,0,X,R"));
+}
+
+/// Define uma máquina não determinística que aceita cadeias contendo `11`, adivinhando
+/// onde a subcadeia começa.
+static NONDET_JSON: &str = r#"{"alphabet":["0","1","B"],"blank_symbol":"B","input_symbols":["0","1"],"states":["q0","q1","q2"],"initial_state":"q0","final_states":["q2"],"transitions":[{"from_state":"q0","read_symbol":"0","write_symbol":"0","move_to":"R","next_state":"q0"},{"from_state":"q0","read_symbol":"1","write_symbol":"1","move_to":"R","next_state":"q0"},{"from_state":"q0","read_symbol":"1","write_symbol":"1","move_to":"R","next_state":"q1"},{"from_state":"q1","read_symbol":"1","write_symbol":"1","move_to":"R","next_state":"q2"}]}"#;
+
+#[test]
+fn test_nondeterministic() {
+    let septuple = nondet::NondetSeptuple::from_json(NONDET_JSON).unwrap();
+    assert!(septuple.valid().is_ok());
+    assert!(Septuple::from_json(NONDET_JSON).is_err());
+
+    let tm = nondet::NondetMachine::new(&septuple, "0110".chars()).unwrap();
+    let run = tm.run();
+    assert_eq!(run.acceptance, Acceptance::Accepted);
+    let path = run.path.unwrap();
+    let states: Vec<_> = path.iter().map(|step| &step.state[..]).collect();
+    assert_eq!(states, vec!["q0", "q0", "q1", "q2"]);
+    assert_eq!(path[2].transition.as_ref().unwrap().read_symbol, '1');
+
+    let tm = nondet::NondetMachine::new(&septuple, "0101".chars()).unwrap();
+    let run = tm.run();
+    assert_eq!(
+        run.acceptance,
+        Acceptance::Rejected(Rejection::NoTransition)
+    );
+    assert_eq!(run.path, None);
+
+    let mut tm = nondet::NondetMachine::new(&septuple, "0101".chars()).unwrap();
+    tm.set_limit(Some(3));
+    assert_eq!(tm.run().acceptance, Acceptance::Exhausted);
+
+    // a repetição de uma configuração de um ancestral indica um laço
+    let septuple = nondet::NondetSeptuple::from_json(LOOP_JSON).unwrap();
+    let tm = nondet::NondetMachine::new(&septuple, vec!['0']).unwrap();
+    assert_eq!(tm.run().acceptance, Acceptance::Looping);
+
+    // classes de mesma precedência que se sobrepõem geram ramos em vez de erros
+    let json = r#"{"alphabet":["0","1","B"],"blank_symbol":"B","input_symbols":["0","1"],"states":["q0","q1","q2"],"initial_state":"q0","final_states":["q2"],"transitions":[{"from_state":"q0","read_symbol":{"in":["0","1"]},"write_symbol":null,"move_to":"R","next_state":"q1"},{"from_state":"q0","read_symbol":{"in":["1"]},"write_symbol":null,"move_to":"R","next_state":"q2"}]}"#;
+    let septuple = nondet::NondetSeptuple::from_json(json).unwrap();
+    assert!(septuple.valid().is_ok());
+    assert!(Septuple::from_json(json).unwrap().valid().is_err());
+    let tm = nondet::NondetMachine::new(&septuple, "1".chars()).unwrap();
+    assert_eq!(tm.run().acceptance, Acceptance::Accepted);
+}
+
+/// Define uma máquina de duas fitas que copia a entrada da primeira fita para a
+/// segunda.
+static COPY_JSON: &str = r#"{"alphabet":["0","1","B"],"blank_symbol":"B","input_symbols":["0","1"],"states":["q0","q1"],"initial_state":"q0","final_states":["q1"],"tapes":2,"transitions":[{"from_state":"q0","read_symbols":["0","B"],"write_symbols":["0","0"],"move_to":["R","R"],"next_state":"q0"},{"from_state":"q0","read_symbols":["1","B"],"write_symbols":["1","1"],"move_to":["R","R"],"next_state":"q0"},{"from_state":"q0","read_symbols":["B","B"],"write_symbols":["B","B"],"move_to":[null,"L"],"next_state":"q1"}]}"#;
+
+#[test]
+fn test_multi_tape() {
+    assert!(multi::MultiSeptuple::declared_in(
+        format::Format::Json,
+        COPY_JSON
+    ));
+    assert!(!multi::MultiSeptuple::declared_in(
+        format::Format::Json,
+        JSON
+    ));
+
+    let septuple = multi::MultiSeptuple::from_json(COPY_JSON).unwrap();
+    assert!(septuple.valid().is_ok());
+    let order: Vec<_> = septuple
+        .sorted_transitions()
+        .into_iter()
+        .map(|(_, read, _)| symbol::join(read))
+        .collect();
+    assert_eq!(order, ["0B", "1B", "BB"]);
+
+    let mut tm = multi::MultiMachine::new(&septuple, vec!['0', '1', '1']).unwrap();
+    assert_eq!(tm.run(), Acceptance::Accepted);
+    assert_eq!(tm.tapes()[0], vec!['0', '1', '1', 'B']);
+    assert_eq!(tm.tapes()[1], vec!['0', '1', '1', 'B']);
+    assert_eq!(tm.current_positions(), vec![3, 2]);
+
+    while tm.undo_transition().is_ok() {}
+    assert_eq!(tm.tapes()[0], vec!['0', '1', '1']);
+    assert_eq!(tm.tapes()[1], vec!['B']);
+    assert_eq!(tm.current_positions(), vec![0, 0]);
+    assert_eq!(tm.current_state(), &septuple.septuple.initial_state);
+
+    // com as fitas infinitas à esquerda, a entrada é copiada em ordem inversa
+    let json = COPY_JSON.replace(r#""tapes":2"#, r#""tapes":2,"two_way_tape":true"#);
+    let json = json.replace(r#"[null,"L"]"#, r#"[null,"R"]"#);
+    let json = json.replace(
+        r#"["R","R"],"next_state":"q0"}"#,
+        r#"["R","L"],"next_state":"q0"}"#,
+    );
+    let septuple = multi::MultiSeptuple::from_json(&json).unwrap();
+    let mut tm = multi::MultiMachine::new(&septuple, vec!['0', '1']).unwrap();
+    assert_eq!(tm.run(), Acceptance::Accepted);
+    assert_eq!(tm.tapes()[1], vec!['B', '1', '0']);
+    assert_eq!(tm.current_positions(), vec![2, -1]);
+    assert_eq!(tm.head_indices(), vec![2, 1]);
+    assert_eq!(tm.origins(), vec![0, 2]);
+
+    while tm.undo_transition().is_ok() {}
+    assert_eq!(tm.tapes()[1], vec!['B']);
+    assert_eq!(tm.current_positions(), vec![0, 0]);
+
+    // máquinas multifita também podem ser definidas em YAML e TOML
+    assert!(multi::MultiSeptuple::declared_in(
+        format::Format::Yaml,
+        COPY_JSON
+    ));
+    let yaml = multi::MultiSeptuple::import(format::Format::Yaml, COPY_JSON).unwrap();
+    let json = multi::MultiSeptuple::from_json(COPY_JSON).unwrap();
+    assert_eq!(yaml.transition_map, json.transition_map);
+    // no TOML, que não possui valor nulo, `"S"` mantém a cabeça parada
+    let toml = r#"
+alphabet = ["0", "1", "B"]
+blank_symbol = "B"
+input_symbols = ["0", "1"]
+states = ["q0", "q1"]
+initial_state = "q0"
+final_states = ["q1"]
+tapes = 2
+
+[[transitions]]
+from_state = "q0"
+read_symbols = ["0", "B"]
+write_symbols = ["0", "0"]
+move_to = ["R", "R"]
+next_state = "q0"
+
+[[transitions]]
+from_state = "q0"
+read_symbols = ["1", "B"]
+write_symbols = ["1", "1"]
+move_to = ["R", "R"]
+next_state = "q0"
+
+[[transitions]]
+from_state = "q0"
+read_symbols = ["B", "B"]
+write_symbols = ["B", "B"]
+move_to = ["S", "L"]
+next_state = "q1"
+"#;
+    assert!(multi::MultiSeptuple::declared_in(
+        format::Format::Toml,
+        toml
+    ));
+    let septuple = multi::MultiSeptuple::import(format::Format::Toml, toml).unwrap();
+    assert_eq!(septuple.transition_map, json.transition_map);
+    let mut tm = multi::MultiMachine::new(&septuple, vec!['0', '1']).unwrap();
+    assert_eq!(tm.run(), Acceptance::Accepted);
+    assert_eq!(tm.tapes()[1], vec!['0', '1', 'B']);
+
+    // todos os erros de transição são reportados, na ordem de declaração
+    let json = COPY_JSON.replace(
+        r#"["1","B"],"write_symbols":["1","1"]"#,
+        r#"["0","B"],"write_symbols":["1","1"]"#,
+    );
+    let json = json.replace(r#"["B","B"],"write_symbols"#, r#"["B"],"write_symbols"#);
+    match multi::MultiSeptuple::from_json(&json) {
+        Err(multi::MultiLoadError::Transitions(errors)) => assert_eq!(
+            errors,
+            vec![
+                multi::MultiTransitionError::Conflict {
+                    first: 0,
+                    second: 1
+                },
+                multi::MultiTransitionError::TapeCount { index: 2 },
+            ]
+        ),
+        other => panic!("esperado erro de transições, obtido {other:?}"),
+    }
+}
+
+/// Define uma máquina que escreve dois símbolos à esquerda da fita original. Só aceita
+/// caso a fita seja infinita em ambas as direções.
+static TWO_WAY_JSON: &str = r#"{"alphabet":["0","X","Y","B"],"blank_symbol":"B","input_symbols":["0"],"states":["q0","q1","q2","q3"],"initial_state":"q0","final_states":["q3"],"two_way_tape":true,"transitions":[{"from_state":"q0","read_symbol":"0","write_symbol":"0","move_to":"L","next_state":"q1"},{"from_state":"q1","read_symbol":"B","write_symbol":"X","move_to":"L","next_state":"q2"},{"from_state":"q2","read_symbol":"B","write_symbol":"Y","move_to":null,"next_state":"q3"}]}"#;
+
+#[test]
+fn test_two_way_tape() {
+    let mut septuple = Septuple::from_json(TWO_WAY_JSON).unwrap();
+    assert!(septuple.two_way_tape);
+
+    let mut tm = Machine::new(&septuple, vec!['0']).unwrap();
+    tm.set_loop_detection(true);
+    assert_eq!(tm.run(), Acceptance::Accepted);
+    assert_eq!(tm.tape(), vec!['Y', 'X', '0']);
+    assert_eq!(tm.current_position(), -2);
+    assert_eq!(tm.head_index(), 0);
+    assert_eq!(tm.origin(), 2);
+
+    while tm.undo_transition().is_ok() {}
+    assert_eq!(tm.tape(), vec!['0']);
+    assert_eq!(tm.current_position(), 0);
+    assert_eq!(tm.origin(), 0);
+
+    septuple.two_way_tape = false;
+    let mut tm = Machine::new(&septuple, vec!['0']).unwrap();
+    assert_eq!(tm.run(), Acceptance::Rejected(Rejection::LeftEnd));
+
+    // a fita cresce à esquerda por várias reservas de brancos
+    let json = r#"{"alphabet":["0","X","B"],"blank_symbol":"B","input_symbols":["0"],"states":["q0"],"initial_state":"q0","final_states":[],"two_way_tape":true,"transitions":[{"from_state":"q0","read_symbol":"0","write_symbol":"X","move_to":"L","next_state":"q0"},{"from_state":"q0","read_symbol":"B","write_symbol":"X","move_to":"L","next_state":"q0"}]}"#;
+    let septuple = Septuple::from_json(json).unwrap();
+    let mut tm = Machine::new(&septuple, vec!['0']).unwrap();
+    tm.set_step_limit(Some(100));
+    assert_eq!(tm.run(), Acceptance::Exhausted);
+    assert_eq!(tm.tape().len(), 101);
+    assert!(tm.tape()[1..].iter().all(|symbol| *symbol == 'X'));
+    assert_eq!(tm.current_position(), -100);
+    assert_eq!((tm.head_index(), tm.origin()), (0, 100));
+
+    while tm.undo_transition().is_ok() {}
+    assert_eq!(tm.tape(), vec!['0']);
+    assert_eq!((tm.head_index(), tm.origin()), (0, 0));
+}
+
+/// Define uma máquina que rejeita explicitamente cadeias iniciadas por 1 e que para, sem
+/// veredito, ao encontrar o primeiro branco após uma sequência de zeros. O estado de
+/// rejeição possui uma transição, que nunca deve ser aplicada.
+static HALTING_JSON: &str = r#"{"alphabet":["0","1","B"],"blank_symbol":"B","input_symbols":["0","1"],"states":["q0","qr","qh"],"initial_state":"q0","final_states":[],"reject_states":["qr"],"halt_states":["qh"],"transitions":[{"from_state":"q0","read_symbol":"0","write_symbol":"0","move_to":"R","next_state":"q0"},{"from_state":"q0","read_symbol":"1","write_symbol":"1","move_to":"R","next_state":"qr"},{"from_state":"q0","read_symbol":"B","write_symbol":"B","move_to":null,"next_state":"qh"},{"from_state":"qr","read_symbol":"B","write_symbol":"B","move_to":"R","next_state":"qr"}]}"#;
+
+#[test]
+fn test_halting_states() {
+    let septuple = Septuple::from_json(HALTING_JSON).unwrap();
+    assert!(septuple.valid().is_ok());
+
+    let mut tm = Machine::new(&septuple, vec!['1']).unwrap();
+    assert_eq!(tm.run(), Acceptance::Rejected(Rejection::RejectState));
+    assert_eq!(tm.steps(), 1);
+
+    let mut tm = Machine::new(&septuple, vec!['0', '0']).unwrap();
+    assert_eq!(tm.run(), Acceptance::Halted);
+    assert!(tm.acceptance().unwrap().halted());
+
+    let mut tm = Machine::new(&septuple, vec!['0', '1']).unwrap();
+    assert_eq!(tm.run(), Acceptance::Rejected(Rejection::RejectState));
+}
+
+#[test]
+fn test_breakpoints() {
+    use breakpoint::Breakpoint;
+
+    let breakpoints: Vec<Breakpoint> = ["q2", "q1 Y", "#3", "@-1"]
+        .iter()
+        .map(|s| s.parse().unwrap())
+        .collect();
+    assert_eq!(
+        breakpoints,
+        vec![
+            Breakpoint::State("q2".to_string()),
+            Breakpoint::Symbol {
+                state: "q1".to_string(),
+                symbol: 'Y'.into()
+            },
+            Breakpoint::Step(3),
+            Breakpoint::Position(-1),
+        ]
+    );
+    assert_eq!(
+        "q1 YY".parse::<Breakpoint>().unwrap(),
+        Breakpoint::Symbol {
+            state: "q1".to_string(),
+            symbol: "YY".into()
+        }
+    );
+    assert!("q1 Y Y".parse::<Breakpoint>().is_err());
+    assert!("#x".parse::<Breakpoint>().is_err());
+
+    // estados e símbolos são verificados na sétupla
+    let septuple = Septuple::from_json(JSON).unwrap();
+    assert!(breakpoints.iter().all(|b| b.valid(&septuple).is_ok()));
+    let invalid = |s: &str| {
+        s.parse::<Breakpoint>()
+            .unwrap()
+            .valid(&septuple)
+            .unwrap_err()
+    };
+    assert_eq!(
+        invalid("q9"),
+        breakpoint::InvalidBreakpointError::UnknownState("q9".to_string())
+    );
+    assert_eq!(
+        invalid("q1 Z"),
+        breakpoint::InvalidBreakpointError::UnknownSymbol('Z'.into())
+    );
+
+    // 0011: q0 -> q1 -> q1 -> q2 (lendo 0 após escrever Y)
+    let mut tm = Machine::new(&septuple, vec!['0', '0', '1', '1']).unwrap();
+    let mut hits = vec![];
+    while tm.transition().is_none() {
+        if let Some(hit) = breakpoints.iter().find(|b| b.hit(&tm)) {
+            hits.push((tm.steps(), hit.to_string()));
+        }
+    }
+    assert_eq!(hits[0], (3, "q2".to_string()));
+    assert!(hits.iter().all(|(_, hit)| hit != "@-1"));
+}
+
+#[test]
+fn test_seek() {
+    let septuple = Septuple::from_json(JSON).unwrap();
+    let initial_tape = vec!['0', '0', '1', '1'];
+    let mut tm = Machine::new(&septuple, initial_tape.clone()).unwrap();
+
+    assert_eq!(tm.seek(5), 5);
+    let tape = tm.tape.clone();
+    assert_eq!(tm.seek(0), 0);
+    assert_eq!(tm.tape(), initial_tape);
+    assert_eq!(tm.current_state, &septuple.initial_state);
+
+    // a máquina aceita a fita antes do passo 1000
+    let halted = tm.seek(1000);
+    assert!(halted < 1000);
+    assert_eq!(tm.acceptance(), Some(Acceptance::Accepted));
+    assert_eq!(tm.seek(5), 5);
+    assert_eq!(tm.tape(), tape);
+}
+
+#[test]
+fn test_dot_export() {
+    let septuple = Septuple::from_json(JSON).unwrap();
+    let dot = format::dot::to_dot(&septuple);
+
+    assert!(dot.starts_with("digraph {\n"));
+    assert!(dot.contains("__start -> \"q0\";"));
+    assert!(dot.contains("\"q3\" [shape=doublecircle];"));
+    // transições paralelas são agrupadas em uma única aresta
+    assert!(dot.contains("\"q1\" -> \"q1\" [label=\"0→0,R\\nY→Y,R\"];"));
+    assert_eq!(dot.matches("\"q1\" -> \"q1\"").count(), 1);
+}
+
+static JFF: &str = r#"<?xml version="1.0" encoding="UTF-8" standalone="no"?><!--Created with JFLAP 7.1.--><structure>
+	<type>turing</type>
+	<automaton>
+		<state id="0" name="q0"><x>50.0</x><y>50.0</y><initial/></state>
+		<state id="1" name="q1"><x>150.0</x><y>50.0</y><final/></state>
+		<transition><from>0</from><to>0</to><read>B</read><write>a</write><move>R</move></transition>
+		<transition><from>0</from><to>1</to><read/><write>B</write><move>S</move></transition>
+	</automaton>
+</structure>"#;
+
+#[test]
+fn test_jflap() {
+    let septuple = Septuple::from_json(JSON).unwrap();
+    let (imported, duplicates) =
+        format::jflap::from_jff(&format::jflap::to_jff(&septuple)).unwrap();
+    assert!(duplicates.is_empty());
+    assert_eq!(imported.states, septuple.states);
+    assert_eq!(imported.final_states, septuple.final_states);
+    assert_eq!(imported.blank_symbol, 'B');
+    assert_eq!(imported.transition_map, septuple.transition_map);
+    assert!(imported.valid().is_ok());
+
+    // a fita finita à esquerda não é representada no JFLAP
+    let (_, warnings) = format::Format::Jflap.export(&septuple);
+    assert_eq!(
+        warnings,
+        vec![format::FormatWarning::OneWayTape(format::Format::Jflap)]
+    );
+
+    // `B` é usado como símbolo, então outro branco é escolhido
+    let (septuple, _) = format::jflap::from_jff(JFF).unwrap();
+    assert!(septuple.valid().is_ok());
+    assert_eq!(septuple.blank_symbol, '□');
+    assert!(septuple.two_way_tape);
+    assert!(format::Format::Jflap.export(&septuple).1.is_empty());
+    let mut machine = Machine::new(&septuple, vec!['B', 'B']).unwrap();
+    assert_eq!(machine.run(), Acceptance::Accepted);
+    assert_eq!(machine.tape(), &['a', 'a', 'B']);
+
+    // estados de mesmo nome não são mesclados
+    let duplicate = JFF.replace(r#"name="q1""#, r#"name="q0""#);
+    let error = format::jflap::from_jff(&duplicate).unwrap_err();
+    assert_eq!(error.to_string(), "mais de um estado possui o nome \"q0\"");
+}
+
+static TEXT: &str = "// substitui todos os símbolos por X e aceita no primeiro branco
+blank: B
+final: q1
+input: 0 1
+
+q0 B -> q1 B S
+q0 * -> q0 X R  // qualquer símbolo exceto B
+";
+
+#[test]
+fn test_text() {
+    let septuple = Septuple::from_json(JSON).unwrap();
+    let (imported, _) = format::text::from_text(&format::text::to_text(&septuple)).unwrap();
+    assert_eq!(imported.alphabet, septuple.alphabet);
+    assert_eq!(imported.input_symbols, septuple.input_symbols);
+    assert_eq!(imported.states, septuple.states);
+    assert_eq!(imported.transition_map, septuple.transition_map);
+
+    let (septuple, _) = format::text::from_text(TEXT).unwrap();
+    assert!(septuple.valid().is_ok());
+    assert_eq!(septuple.initial_state, "q0");
+    assert_eq!(septuple.transition_map.len(), 1);
+    assert_eq!(septuple.class_map["q0"].len(), 1);
+    let mut machine = Machine::new(&septuple, vec!['0', '1']).unwrap();
+    assert_eq!(machine.run(), Acceptance::Accepted);
+    assert_eq!(machine.tape(), &['X', 'X', 'B']);
+
+    let error = |text| format::text::from_text(text).unwrap_err().to_string();
+    assert_eq!(
+        error("q0 0 -> q1 X R\n  q0 1 => q1 X R"),
+        "linha 2, coluna 8: \"->\" esperado, encontrado \"=>\""
+    );
+    assert_eq!(
+        error("q0 0 -> q1 X"),
+        "linha 1, coluna 13: movimento esperado"
+    );
+    assert_eq!(
+        error("q0 0 -> q1 X R\nq0 0 -> q2 X R"),
+        "linha 2, coluna 1: transição conflitante com a declarada na linha 1"
+    );
+
+    // transições duplicadas são localizadas por suas linhas
+    let (_, duplicates) = format::text::from_text("q0 0 -> q1 X R\n\nq0 0 -> q1 X R").unwrap();
+    assert_eq!(
+        duplicates[0].to_string(),
+        "transições das linhas 1 e 3 para (q0, 0)"
+    );
+
+    // os erros de validação indicam a linha da transição
+    let text = TEXT.replace("input: 0 1\n", "input: 0 1\nstates: q0 q1\n");
+    let text = text.replace("q0 * -> q0", "q0 * -> q2");
+    let (septuple, _) = format::text::from_text(&text).unwrap();
+    let errors: Vec<_> = septuple
+        .valid()
+        .unwrap_err()
+        .iter()
+        .map(ToString::to_string)
+        .collect();
+    assert_eq!(
+        errors,
+        ["transição da linha 8: estado \"q2\" não está contido no conjunto de estados"]
+    );
+}
+
+static MORPHETT: &str = "; substitui a por b até o branco
+0 a b r 0
+0 c * r 0
+0 _ _ * halt-accept
+* * * * halt-reject  ; qualquer outra configuração
+";
+
+static TMIO: &str = "name: binary increment
+input: '1011'
+blank: ' '
+start state: right
+table:
+  right:
+    [1,0]: R
+    ' '  : {L: carry}
+  carry:
+    1      : {write: 0, L}
+    [0,' ']: {write: 1, L: done}
+  done:
+";
+
+#[test]
+fn test_morphett() {
+    let (septuple, _) = format::morphett::from_morphett(MORPHETT).unwrap();
+    assert!(septuple.valid().is_ok());
+    assert_eq!(septuple.initial_state, "0");
+    assert_eq!(septuple.blank_symbol, '_');
+    assert!(septuple.final_states.contains("halt-accept"));
+    assert!(septuple.reject_states.contains("halt-reject"));
+    // apenas o estado 0 possui transições, uma para cada símbolo
+    assert_eq!(septuple.transition_map.len(), 4);
+
+    let mut machine = Machine::new(&septuple, vec!['a', 'c']).unwrap();
+    assert_eq!(machine.run(), Acceptance::Accepted);
+    assert_eq!(machine.tape(), &['b', 'c', '_']);
+    let mut machine = Machine::new(&septuple, vec!['a', 'b']).unwrap();
+    assert_eq!(machine.run(), Acceptance::Rejected(Rejection::RejectState));
+
+    // o estado inicial e o estado final são renomeados
+    let septuple = Septuple::from_json(JSON).unwrap();
+    let morphett = format::morphett::to_morphett(&septuple);
+    assert!(morphett.contains("\n0 0 X r q1\n"));
+    assert!(morphett.contains("\nq4 _ _ r halt-accept\n"));
+    let (imported, warnings) = format::morphett::from_morphett(&morphett).unwrap();
+    assert!(warnings.is_empty());
+    for (tape, acceptance) in [("0011", true), ("001", false)] {
+        let tape: Vec<_> = tape.chars().collect();
+        let mut machine = Machine::new(&imported, tape).unwrap();
+        assert_eq!(machine.run() == Acceptance::Accepted, acceptance);
+    }
+
+    // um estado inicial de aceitação mantém a aceitação imediata
+    let mut septuple = Septuple::from_json(JSON).unwrap();
+    septuple.final_states.insert("q0".to_string());
+    let morphett = format::morphett::to_morphett(&septuple);
+    assert!(morphett.contains("\n0 * * * halt-accept\n"));
+    let (imported, _) = format::morphett::from_morphett(&morphett).unwrap();
+    let mut machine = Machine::new(&imported, Vec::<char>::new()).unwrap();
+    assert_eq!(machine.run(), Acceptance::Accepted);
+
+    // sem o estado 0, a máquina inicia no estado da primeira linha
+    let (septuple, warnings) =
+        format::morphett::from_morphett(&MORPHETT.replace('0', "s")).unwrap();
+    assert_eq!(septuple.initial_state, "s");
+    assert_eq!(
+        warnings,
+        vec![format::FormatWarning::InitialState {
+            expected: "0",
+            used: "s".to_string(),
+        }]
+    );
+}
+
+#[test]
+fn test_tmio() {
+    let (septuple, _) = format::tmio::from_tmio(TMIO).unwrap();
+    assert!(septuple.valid().is_ok());
+    assert_eq!(septuple.blank_symbol, ' ');
+    assert_eq!(septuple.halt_states.iter().collect::<Vec<_>>(), ["done"]);
+    let mut machine = Machine::new(&septuple, "1011".chars()).unwrap();
+    assert_eq!(machine.run(), Acceptance::Halted);
+    assert_eq!(symbol::join(machine.tape()).trim(), "1100");
+
+    let yaml = format::tmio::to_tmio(&septuple);
+    let (reloaded, _) = format::tmio::from_tmio(&yaml).unwrap();
+    assert_eq!(reloaded.states, septuple.states);
+    assert_eq!(reloaded.transition_map, septuple.transition_map);
+
+    // o mesmo símbolo não pode ser lido duas vezes no mesmo estado
+    let conflicting = TMIO.replace("    ' '  : {L: carry}", "    ' '  : {L: carry}\n    0: L");
+    let error = format::tmio::from_tmio(&conflicting).unwrap_err();
+    assert_eq!(
+        error.to_string(),
+        "símbolo 0 lido mais de uma vez no estado right"
+    );
+
+    // a transição sem movimento passa por um estado auxiliar que retorna a cabeça
+    let septuple = Septuple::from_json(TWO_WAY_JSON).unwrap();
+    let yaml = format::tmio::to_tmio(&septuple);
+    assert!(yaml.contains("    'B': {write: 'Y', R: 'q3'''}\n"));
+    assert!(yaml.contains("  'q3''':\n    ['0', 'X', 'Y', 'B']: {L: 'q3'}\n"));
+    let (reloaded, _) = format::tmio::from_tmio(&yaml).unwrap();
+    let mut machine = Machine::new(&reloaded, vec!['0']).unwrap();
+    assert_eq!(machine.run(), Acceptance::Halted);
+    assert_eq!(machine.tape(), vec!['Y', 'X', '0']);
+    assert_eq!(machine.current_position(), -2);
+}
+
+/// Define uma máquina que troca `a` por `b` e `b` por `a`, mantém os demais símbolos não
+/// brancos e aceita no primeiro branco, usando classes de símbolos.
+static CLASSES_JSON: &str = r#"{"alphabet":["a","b","c","d","B"],"blank_symbol":"B","input_symbols":["a","b","c","d"],"states":["q0","q1"],"initial_state":"q0","final_states":["q1"],"transitions":[{"from_state":"q0","read_symbol":"any","move_to":"R","next_state":"q0"},{"from_state":"q0","read_symbol":{"except":["a","b","c","d"]},"move_to":null,"next_state":"q1"},{"from_state":"q0","read_symbol":{"in":["a"]},"write_symbol":"b","move_to":"R","next_state":"q0"},{"from_state":"q0","read_symbol":"b","write_symbol":"a","move_to":"R","next_state":"q0"}]}"#;
+
+#[test]
+fn test_symbol_classes() {
+    let septuple = Septuple::from_json(CLASSES_JSON).unwrap();
+    assert!(septuple.valid().is_ok());
+
+    // exata > in > except > any
+    let q0 = "q0".to_string();
+    assert_eq!(
+        septuple
+            .find_transition(&q0, &'b'.into())
+            .unwrap()
+            .write_symbol,
+        Some('a'.into())
+    );
+    assert_eq!(
+        septuple
+            .find_transition(&q0, &'a'.into())
+            .unwrap()
+            .write_symbol,
+        Some('b'.into())
+    );
+    assert_eq!(
+        septuple
+            .find_transition(&q0, &'B'.into())
+            .unwrap()
+            .next_state,
+        "q1"
+    );
+    assert_eq!(
+        septuple.find_transition(&q0, &'c'.into()).unwrap().move_to,
+        Some(Movement::R)
+    );
+
+    let mut machine = Machine::new(&septuple, "abcd".chars()).unwrap();
+    assert_eq!(machine.run(), Acceptance::Accepted);
+    assert_eq!(machine.tape(), &['b', 'a', 'c', 'd', 'B']);
+
+    // as classes são mantidas na exportação
+    let exported = Septuple::from_json(&septuple.to_json()).unwrap();
+    assert_eq!(exported.class_map, septuple.class_map);
+    let (text, _) = format::text::from_text(&format::text::to_text(&septuple)).unwrap();
+    assert_eq!(text.class_map["q0"].len(), 3);
+
+    // duas classes de mesma precedência não podem tratar o mesmo símbolo
+    let ambiguous = CLASSES_JSON.replace(r#"{"in":["a"]}"#, r#"{"except":["a"]}"#);
+    let septuple = Septuple::from_json(&ambiguous).unwrap();
+    assert_eq!(
+        septuple.valid().unwrap_err(),
+        vec![sep::SepError::AmbiguousTransitions {
+            state: q0,
+            symbol: 'B'.into(),
+            first: Some(sep::Location::Index(1)),
+            second: Some(sep::Location::Index(2)),
+        }]
+    );
+}
+
+/// Define uma máquina com símbolos de mais de um caractere, que troca cada `X1` por `#0`
+/// e aceita no primeiro branco.
+static NAMED_JSON: &str = r##"{"alphabet":["X1","#0","blank","any"],"blank_symbol":"blank","input_symbols":["X1","#0","any"],"states":["q0","q1"],"initial_state":"q0","final_states":["q1"],"transitions":[{"from_state":"q0","read_symbol":"X1","write_symbol":"#0","move_to":"R","next_state":"q0"},{"from_state":"q0","read_symbol":{"in":["#0"]},"move_to":"R","next_state":"q0"},{"from_state":"q0","read_symbol":{"symbol":"any"},"move_to":"R","next_state":"q0"},{"from_state":"q0","read_symbol":"blank","move_to":null,"next_state":"q1"}]}"##;
+
+#[test]
+fn test_named_symbols() {
+    let septuple = Septuple::from_json(NAMED_JSON).unwrap();
+    assert!(septuple.valid().is_ok());
+
+    let tape = symbol::split_input("X1 #0,X1 any", &septuple.input_symbols);
+    assert_eq!(symbol::join(&tape), "X1 #0 X1 any");
+    let mut machine = Machine::new(&septuple, tape).unwrap();
+    assert_eq!(machine.run(), Acceptance::Accepted);
+    assert_eq!(symbol::join(machine.tape()), "#0 #0 #0 any blank");
+
+    // sem símbolos de mais de um caractere, a cadeia é lida caractere a caractere
+    let input_symbols: indexmap::IndexSet<Symbol> = ['0', '1'].map(Symbol::from).into();
+    assert_eq!(symbol::split_input("01", &input_symbols), vec!['0', '1']);
+    assert_eq!(symbol::split_input("0 1", &input_symbols), vec!['0', '1']);
+    assert!(Machine::new(&septuple, ["X1X1"]).is_err());
+
+    // o símbolo `any` não é confundido com a classe de mesmo nome
+    let exported = Septuple::from_json(&septuple.to_json()).unwrap();
+    assert_eq!(exported.transition_map, septuple.transition_map);
+    assert_eq!(exported.class_map, septuple.class_map);
+    let (text, _) = format::text::from_text(&format::text::to_text(&septuple)).unwrap();
+    assert_eq!(text.transition_map, septuple.transition_map);
+}
@@ -0,0 +1,230 @@
+//! Registro passo a passo do processamento de uma [`Machine`], com exportação para
+//! texto, JSON lines e CSV.
+
+use std::{fmt::Display, io::Write, str::FromStr};
+
+use serde::{Serialize, Serializer};
+
+use super::{
+    sep::Movement,
+    symbol::{self, Symbol},
+    Machine,
+};
+use crate::Result;
+
+/// Uma configuração da máquina em um passo do processamento.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize)]
+pub struct Step {
+    /// Número de transições aplicadas até essa configuração.
+    pub step: usize,
+    pub state: String,
+    /// Posição da cabeça, relativa ao início da fita original.
+    pub position: isize,
+    /// Posição do primeiro símbolo de `tape`, relativa ao início da fita original. Só é
+    /// negativa em fitas infinitas em ambas as direções.
+    pub tape_start: isize,
+    /// Exportada como texto, no formato de [`symbol::join`].
+    #[serde(serialize_with = "serialize_tape")]
+    pub tape: Vec<Symbol>,
+    /// A transição que levou a essa configuração. É [`None`] no passo inicial.
+    pub transition: Option<StepTransition>,
+}
+
+/// A transição aplicada para se chegar em um [`Step`].
+#[derive(Clone, Debug, PartialEq, Eq, Serialize)]
+pub struct StepTransition {
+    pub from_state: String,
+    pub read_symbol: Symbol,
+    pub write_symbol: Symbol,
+    pub move_to: Option<Movement>,
+}
+
+impl Step {
+    /// Constrói o passo que representa a configuração atual de `machine`.
+    pub fn new(machine: &Machine) -> Step {
+        let transition = machine.last_applied().map(|applied| StepTransition {
+            from_state: applied.from_state.clone(),
+            write_symbol: applied.transition.written(&applied.read_symbol),
+            read_symbol: applied.read_symbol,
+            move_to: applied.transition.move_to,
+        });
+
+        Step {
+            step: machine.steps(),
+            state: machine.current_state().clone(),
+            position: machine.current_position(),
+            tape_start: -(machine.origin() as isize),
+            tape: machine.tape().to_vec(),
+            transition,
+        }
+    }
+}
+
+/// Iterador retornado por [`Machine::trace`].
+pub struct Trace<'t, 'machine> {
+    machine: &'t mut Machine<'machine>,
+    started: bool,
+}
+
+impl<'t, 'machine> Trace<'t, 'machine> {
+    pub(super) fn new(machine: &'t mut Machine<'machine>) -> Self {
+        Trace {
+            machine,
+            started: false,
+        }
+    }
+}
+
+impl Iterator for Trace<'_, '_> {
+    type Item = Step;
+
+    fn next(&mut self) -> Option<Step> {
+        if !self.started {
+            self.started = true;
+            return Some(Step::new(self.machine));
+        }
+        match self.machine.transition() {
+            Some(_) => None,
+            None => Some(Step::new(self.machine)),
+        }
+    }
+}
+
+/// Os formatos nos quais um registro pode ser exportado.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Format {
+    /// Uma linha legível por passo, com a cabeça da máquina entre colchetes.
+    Text,
+    /// Um objeto JSON por linha.
+    JsonLines,
+    /// CSV com cabeçalho.
+    Csv,
+}
+
+/// Erro retornado ao interpretar um [`Format`] desconhecido.
+#[derive(Debug)]
+pub struct UnknownFormatError(String);
+
+impl Display for UnknownFormatError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "formato de registro desconhecido: {}", self.0)
+    }
+}
+impl std::error::Error for UnknownFormatError {}
+
+impl FromStr for Format {
+    type Err = UnknownFormatError;
+
+    fn from_str(s: &str) -> std::result::Result<Self, Self::Err> {
+        match s {
+            "text" => Ok(Format::Text),
+            "json" => Ok(Format::JsonLines),
+            "csv" => Ok(Format::Csv),
+            _ => Err(UnknownFormatError(s.to_string())),
+        }
+    }
+}
+
+/// Escreve em `out` todos os passos de `steps` no formato `format`.
+pub fn write<W, I>(format: Format, steps: I, out: &mut W) -> Result<()>
+where
+    W: Write,
+    I: IntoIterator<Item = Step>,
+{
+    if format == Format::Csv {
+        writeln!(
+            out,
+            "step,state,position,tape_start,tape,from_state,read_symbol,write_symbol,move_to"
+        )?;
+    }
+
+    for step in steps {
+        match format {
+            Format::Text => write_text(&step, out)?,
+            Format::JsonLines => {
+                serde_json::to_writer(&mut *out, &step)?;
+                writeln!(out)?;
+            }
+            Format::Csv => write_csv(&step, out)?,
+        }
+    }
+    Ok(())
+}
+
+fn write_text<W: Write>(step: &Step, out: &mut W) -> Result<()> {
+    let head = (step.position - step.tape_start) as usize;
+    let separator = if step.tape.iter().all(Symbol::is_char) {
+        ""
+    } else {
+        " "
+    };
+    let mut tape = String::new();
+    for (i, symbol) in step.tape.iter().enumerate() {
+        if i > 0 {
+            tape.push_str(separator);
+        }
+        if i == head {
+            tape.push_str(&format!("[{}]", symbol));
+        } else {
+            tape.push_str(symbol.as_str());
+        }
+    }
+
+    write!(out, "{}: {} {}", step.step, step.state, tape)?;
+    if let Some(transition) = &step.transition {
+        write!(
+            out,
+            " (δ ({}, {}) = ({}, {}, {}))",
+            transition.from_state,
+            transition.read_symbol,
+            step.state,
+            transition.write_symbol,
+            Movement::symbol(transition.move_to, "-")
+        )?;
+    }
+    writeln!(out)?;
+    Ok(())
+}
+
+fn write_csv<W: Write>(step: &Step, out: &mut W) -> Result<()> {
+    let (from_state, read_symbol, write_symbol, move_to) = match &step.transition {
+        Some(transition) => (
+            csv_field(&transition.from_state),
+            csv_field(&transition.read_symbol.to_string()),
+            csv_field(&transition.write_symbol.to_string()),
+            Movement::symbol(transition.move_to, "-").to_string(),
+        ),
+        None => Default::default(),
+    };
+
+    writeln!(
+        out,
+        "{},{},{},{},{},{},{},{},{}",
+        step.step,
+        csv_field(&step.state),
+        step.position,
+        step.tape_start,
+        csv_field(&symbol::join(&step.tape)),
+        from_state,
+        read_symbol,
+        write_symbol,
+        move_to
+    )?;
+    Ok(())
+}
+
+fn serialize_tape<S: Serializer>(
+    tape: &[Symbol],
+    serializer: S,
+) -> std::result::Result<S::Ok, S::Error> {
+    serializer.serialize_str(&symbol::join(tape))
+}
+
+/// Escapa um campo CSV, envolvendo-o em aspas caso necessário.
+fn csv_field(field: &str) -> String {
+    if field.contains([',', '"', '\n', '\r']) {
+        format!("\"{}\"", field.replace('"', "\"\""))
+    } else {
+        field.to_string()
+    }
+}
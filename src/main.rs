@@ -1,8 +1,16 @@
-use std::{env, fs, io, process};
+use std::{env, fs, io, process, result};
 
 use crossterm::{cursor, terminal};
 use tm::{
-    machine::{sep::Septuple, Machine},
+    batch,
+    machine::{
+        breakpoint::Breakpoint,
+        format,
+        multi::{MultiLoadError, MultiMachine, MultiSeptuple},
+        nondet::NondetSeptuple,
+        sep::{LoadError, SepError, Septuple},
+        trace, Machine, OutputMode, DEFAULT_STEP_LIMIT,
+    },
     state, Result,
 };
 use tui::{
@@ -10,28 +18,330 @@ use tui::{
     Terminal,
 };
 
+/// Mensagem de uso do programa.
+const USAGE: &str = "Uso: tm <sétupla> [--format <formato>] [--max-steps <n>] [--no-loop-detection]
+          [--function [--from-head]]
+          [--batch [--nondeterministic] [--trace <text|json|csv>] [--input <arquivo>] [cadeia...]]
+       tm <sétupla> [--format <formato>] --export <formato|dot>
+Formatos: json, yaml, toml, jff, tm, morphett, tmio";
+
 fn main() -> Result<()> {
-    let args: Vec<String> = env::args().collect();
-    if args.len() != 2 {
-        eprintln!("Passe como argumento apenas o path do arquivo da sétupla");
+    let args = match Args::parse(env::args().skip(1)) {
+        Some(args) => args,
+        None => {
+            eprintln!("{USAGE}");
+            process::exit(1);
+        }
+    };
+    let file_contents = fs::read_to_string(&args.file_path)?;
+    // sem `--format`, arquivos sem extensão conhecida são interpretados como JSON
+    let source = args
+        .format
+        .or_else(|| format::Format::from_path(&args.file_path))
+        .unwrap_or(format::Format::Json);
+    if args.nondeterministic {
+        if source != format::Format::Json {
+            eprintln!("`--nondeterministic` é suportado apenas em sétuplas JSON");
+            process::exit(1);
+        }
+        return run_nondet(&file_contents, args);
+    }
+    if MultiSeptuple::declared_in(source, &file_contents) {
+        return run_multi(source, &file_contents, args);
+    }
+
+    let sep = match source.import(&file_contents) {
+        Ok((sep, warnings)) => {
+            for warning in warnings {
+                eprintln!("Aviso: {warning}");
+            }
+            sep
+        }
+        Err(LoadError::Conflicts(conflicts)) => {
+            for conflict in conflicts {
+                eprintln!("Erro de definição da sétupla: {conflict} são conflitantes");
+            }
+            eprintln!("Para máquinas não determinísticas, use `--batch --nondeterministic`");
+            process::exit(1);
+        }
+        Err(err) => {
+            eprintln!("Erro ao carregar a sétupla: {err}");
+            process::exit(1);
+        }
+    };
+    exit_on_errors(sep.valid());
+
+    match args.mode {
+        Mode::Export(format) => {
+            let (exported, warnings) = format.export(&sep);
+            for warning in warnings {
+                eprintln!("Aviso: {warning}");
+            }
+            print!("{exported}");
+        }
+        Mode::Tui => {
+            if let Err(err) = run_tui(
+                Definition::Single(&sep),
+                args.step_limit,
+                args.detect_loops,
+                args.output,
+            ) {
+                eprintln!("Um erro ocorreu: {err}");
+                process::exit(1)
+            }
+        }
+        Mode::Batch(input) => {
+            let options = batch::Options {
+                step_limit: args.step_limit,
+                detect_loops: args.detect_loops,
+                output: args.output,
+                trace: args.trace,
+            };
+            let inputs = read_inputs(input)?;
+            let summary = batch::run(&sep, inputs, options, &mut io::stdout().lock())?;
+            process::exit(exit_code(summary));
+        }
+    }
+    Ok(())
+}
+
+/// Processa as cadeias em uma máquina não determinística. Esse tipo de máquina só é
+/// suportado no modo não interativo.
+fn run_nondet(file_contents: &str, args: Args) -> Result<()> {
+    let sep = match NondetSeptuple::from_json(file_contents) {
+        Ok(sep) => sep,
+        Err(err) => {
+            eprintln!("Erro ao carregar a sétupla: {err}");
+            process::exit(1);
+        }
+    };
+    exit_on_errors(sep.valid());
+
+    let input = match args.mode {
+        Mode::Batch(input) => input,
+        // `Args::parse` garante que `--nondeterministic` é usado apenas com `--batch`
+        Mode::Tui | Mode::Export(_) => unreachable!(),
+    };
+    let options = batch::Options {
+        step_limit: args.step_limit,
+        trace: args.trace,
+        ..Default::default()
+    };
+    let inputs = read_inputs(input)?;
+    let summary = batch::run_nondet(&sep, inputs, options, &mut io::stdout().lock())?;
+    process::exit(exit_code(summary));
+}
+
+/// Processa as cadeias em uma máquina multifita, definida no formato `source`, na
+/// interface de terminal ou no modo não interativo.
+fn run_multi(source: format::Format, file_contents: &str, args: Args) -> Result<()> {
+    let sep = match MultiSeptuple::import(source, file_contents) {
+        Ok(sep) => sep,
+        Err(MultiLoadError::Transitions(errors)) => {
+            for err in errors {
+                eprintln!("Erro de definição da sétupla: {err}");
+            }
+            process::exit(1);
+        }
+        Err(err) => {
+            eprintln!("Erro ao carregar a sétupla: {err}");
+            process::exit(1);
+        }
+    };
+    exit_on_errors(sep.valid());
+    if args.output.is_some() || args.trace.is_some() {
+        eprintln!("`--function` e `--trace` não são suportados em máquinas multifita");
         process::exit(1);
     }
-    let file_path = &args[1];
-    let file_contents = fs::read_to_string(file_path)?;
-    let sep = Septuple::from_json(&file_contents)?;
-    if let Err(err) = sep.valid() {
-        eprintln!("Erro de definição da sétupla: {err}");
+
+    match args.mode {
+        Mode::Export(_) => {
+            eprintln!("`--export` não é suportado em máquinas multifita");
+            process::exit(1);
+        }
+        Mode::Tui => {
+            if let Err(err) = run_tui(Definition::Multi(&sep), args.step_limit, false, None) {
+                eprintln!("Um erro ocorreu: {err}");
+                process::exit(1)
+            }
+            Ok(())
+        }
+        Mode::Batch(input) => {
+            let options = batch::Options {
+                step_limit: args.step_limit,
+                ..Default::default()
+            };
+            let inputs = read_inputs(input)?;
+            let summary = batch::run_multi(&sep, inputs, options, &mut io::stdout().lock())?;
+            process::exit(exit_code(summary));
+        }
+    }
+}
+
+/// Imprime os erros de validação da sétupla e encerra o programa, caso existam.
+fn exit_on_errors(validation: result::Result<(), Vec<SepError>>) {
+    if let Err(errors) = validation {
+        for err in errors {
+            eprintln!("Erro de definição da sétupla: {err}");
+        }
         process::exit(1);
     }
+}
 
-    if let Err(err) = run_tui(sep) {
-        eprintln!("Um erro ocorreu: {err}");
-        process::exit(1)
+/// Argumentos de linha de comando.
+struct Args {
+    file_path: String,
+    /// Formato do arquivo da sétupla, caso não deva ser deduzido da extensão.
+    format: Option<format::Format>,
+    mode: Mode,
+    /// Limite de passos por cadeia; `--max-steps 0` remove o limite.
+    step_limit: Option<usize>,
+    /// Caso `false`, a detecção de laços é desativada (`--no-loop-detection`).
+    detect_loops: bool,
+    /// Caso [`Some`], a máquina computa uma função (`--function`).
+    output: Option<OutputMode>,
+    /// Formato do registro passo a passo, apenas no modo não interativo.
+    trace: Option<trace::Format>,
+    /// Caso `true`, a sétupla define uma máquina não determinística.
+    nondeterministic: bool,
+}
+
+/// Define como as cadeias serão processadas.
+enum Mode {
+    /// Interface interativa de terminal.
+    Tui,
+    /// Execução não interativa, com a origem das cadeias.
+    Batch(Input),
+    /// Conversão da sétupla para outro formato, impressa na saída padrão.
+    Export(format::Format),
+}
+
+/// Origem das cadeias no modo não interativo.
+enum Input {
+    /// Cadeias passadas diretamente como argumentos.
+    Args(Vec<String>),
+    /// Um arquivo com uma cadeia por linha.
+    File(String),
+    /// A entrada padrão, com uma cadeia por linha.
+    Stdin,
+}
+
+impl Args {
+    /// Interpreta os argumentos do programa (sem o nome do executável). Retorna
+    /// [`None`] caso os argumentos não sigam o [`USAGE`].
+    fn parse(mut args: impl Iterator<Item = String>) -> Option<Args> {
+        let mut file_path = None;
+        let mut batch = false;
+        let mut input_file = None;
+        let mut inputs = vec![];
+        let mut step_limit = Some(DEFAULT_STEP_LIMIT);
+        let mut detect_loops = true;
+        let mut function = false;
+        let mut from_head = false;
+        let mut trace = None;
+        let mut nondeterministic = false;
+        let mut export = None;
+        let mut format = None;
+
+        while let Some(arg) = args.next() {
+            match &arg[..] {
+                "--batch" => batch = true,
+                "--input" => input_file = Some(args.next()?),
+                "--no-loop-detection" => detect_loops = false,
+                "--function" => function = true,
+                "--from-head" => from_head = true,
+                "--trace" => trace = Some(args.next()?.parse().ok()?),
+                "--nondeterministic" => nondeterministic = true,
+                "--export" => export = Some(args.next()?.parse().ok()?),
+                "--format" => format = Some(args.next()?.parse().ok()?),
+                "--max-steps" => {
+                    step_limit = match args.next()?.parse().ok()? {
+                        0 => None,
+                        limit => Some(limit),
+                    }
+                }
+                // opções desconhecidas não são confundidas com o arquivo ou as cadeias
+                _ if arg.starts_with("--") => return None,
+                _ if file_path.is_none() => file_path = Some(arg),
+                _ => inputs.push(arg),
+            }
+        }
+
+        if (trace.is_some() || nondeterministic) && !batch {
+            return None;
+        }
+        let mode = match (batch, input_file) {
+            (false, None) if inputs.is_empty() => match export {
+                Some(format) => Mode::Export(format),
+                None => Mode::Tui,
+            },
+            (false, _) => return None,
+            (true, Some(_)) if !inputs.is_empty() => return None,
+            (true, Some(path)) => Mode::Batch(Input::File(path)),
+            (true, None) if inputs.is_empty() => Mode::Batch(Input::Stdin),
+            (true, None) => Mode::Batch(Input::Args(inputs)),
+        };
+        if export.is_some() && !matches!(mode, Mode::Export(_)) {
+            return None;
+        }
+
+        let output = match (function, from_head) {
+            (true, false) => Some(OutputMode::Trimmed),
+            (true, true) => Some(OutputMode::FromHead),
+            (false, false) => None,
+            (false, true) => return None,
+        };
+        if output.is_some() && nondeterministic {
+            return None;
+        }
+
+        Some(Args {
+            file_path: file_path?,
+            format,
+            mode,
+            step_limit,
+            detect_loops,
+            output,
+            trace,
+            nondeterministic,
+        })
+    }
+}
+
+/// Lê as cadeias a serem processadas no modo não interativo.
+fn read_inputs(input: Input) -> Result<Vec<String>> {
+    let inputs = match input {
+        Input::Args(inputs) => inputs,
+        Input::File(path) => fs::read_to_string(path)?
+            .lines()
+            .map(String::from)
+            .collect(),
+        Input::Stdin => io::stdin().lines().collect::<io::Result<_>>()?,
+    };
+    Ok(inputs)
+}
+
+/// Retorna o código de saída do modo não interativo: `0` caso todas as cadeias sejam
+/// aceitas, `2` caso alguma seja rejeitada, `3` caso a máquina não pare para alguma
+/// delas e `1` caso alguma seja inválida.
+fn exit_code(summary: batch::Summary) -> i32 {
+    if summary.errors > 0 {
+        1
+    } else if summary.undecided > 0 {
+        3
+    } else if summary.rejected > 0 {
+        2
+    } else {
+        0
     }
-    Ok(())
 }
 
-fn run_tui(sep: Septuple) -> Result<()> {
+fn run_tui(
+    sep: Definition,
+    step_limit: Option<usize>,
+    detect_loops: bool,
+    output: Option<OutputMode>,
+) -> Result<()> {
     let mut stdout = io::stdout();
 
     terminal::enable_raw_mode()?;
@@ -41,8 +351,12 @@ fn run_tui(sep: Septuple) -> Result<()> {
 
     let mut app = App {
         term,
-        sep: &sep,
+        sep,
         machine: None,
+        step_limit,
+        detect_loops,
+        output,
+        breakpoints: vec![],
     };
 
     // loop principal -- muda de estado até o usuário sair do programa
@@ -68,19 +382,55 @@ fn run_tui(sep: Septuple) -> Result<()> {
 /// opcional do mesmo tipo.
 struct StateFunction<B: Backend>(fn(&mut App<B>) -> Result<Option<StateFunction<B>>>);
 
+/// A definição da máquina carregada do arquivo da sétupla.
+#[derive(Clone, Copy)]
+enum Definition<'d> {
+    Single(&'d Septuple),
+    Multi(&'d MultiSeptuple),
+}
+
+/// Uma máquina em processamento, de acordo com sua [`Definition`].
+enum AnyMachine<'m> {
+    Single(Machine<'m>),
+    Multi(MultiMachine<'m>),
+}
+
 /// Mantém algumas variáveis que podem ser alteradas pelos estados.
 struct App<'app, B: Backend> {
     term: Terminal<B>,
-    sep: &'app Septuple,
+    sep: Definition<'app>,
     // opcional pois a máquina é inicializada depois
-    machine: Option<Machine<'app>>,
+    machine: Option<AnyMachine<'app>>,
+    step_limit: Option<usize>,
+    detect_loops: bool,
+    output: Option<OutputMode>,
+    /// Pontos de parada da máquina, mantidos entre as fitas processadas.
+    breakpoints: Vec<Breakpoint>,
 }
 
 /// Lê o input para a fita até receber uma fita válida ou sair do programa.
 fn read_tape<B: Backend>(app: &mut App<B>) -> Result<Option<StateFunction<B>>> {
-    match state::read_valid_tape(&mut app.term, &app.sep.input_symbols)? {
+    let input_symbols = match app.sep {
+        Definition::Single(sep) => &sep.input_symbols,
+        Definition::Multi(sep) => &sep.septuple.input_symbols,
+    };
+    match state::read_valid_tape(&mut app.term, input_symbols)? {
         tm::Either::L(tape) => {
-            app.machine = Some(Machine::new(app.sep, tape).unwrap());
+            // a fita lida contém apenas símbolos de entrada; unwrap é seguro
+            let machine = match app.sep {
+                Definition::Single(sep) => {
+                    let mut machine = Machine::new(sep, tape).unwrap();
+                    machine.set_step_limit(app.step_limit);
+                    machine.set_loop_detection(app.detect_loops);
+                    AnyMachine::Single(machine)
+                }
+                Definition::Multi(sep) => {
+                    let mut machine = MultiMachine::new(sep, tape).unwrap();
+                    machine.set_step_limit(app.step_limit);
+                    AnyMachine::Multi(machine)
+                }
+            };
+            app.machine = Some(machine);
 
             // "transforma" [`process_machine`] em uma função do tipo correto.
             let alias: StateFunction<B> = StateFunction(process_machine);
@@ -95,8 +445,14 @@ fn read_tape<B: Backend>(app: &mut App<B>) -> Result<Option<StateFunction<B>>> {
 fn process_machine<B: Backend>(app: &mut App<B>) -> Result<Option<StateFunction<B>>> {
     // essa função só será chamada depois da máquina necessariamente ter sido
     // criada; portanto unwrap é seguro.
-    let machine = app.machine.as_mut().unwrap();
-    state::process_machine(&mut app.term, machine)?;
+    match app.machine.as_mut().unwrap() {
+        AnyMachine::Single(machine) => {
+            state::process_machine(&mut app.term, machine, app.output, &mut app.breakpoints)?;
+        }
+        AnyMachine::Multi(machine) => {
+            state::process_multi_machine(&mut app.term, machine)?;
+        }
+    }
 
     // "transforma" [`process_machine`] em uma função do tipo correto.
     let alias: StateFunction<B> = StateFunction(read_tape);
@@ -0,0 +1,128 @@
+//! Reprodução automática da tela de processamento, compartilhada pelos estados em
+//! [`state`](crate::state) e exibida por [`draw`](crate::draw).
+
+use std::time::{Duration, Instant};
+
+use crossterm::event::{KeyCode, KeyEvent};
+
+/// Velocidades da reprodução automática, em transições por segundo.
+const SPEEDS: [u32; 10] = [1, 2, 5, 10, 20, 50, 100, 200, 500, 1000];
+/// Velocidade inicial da reprodução automática, como índice de [`SPEEDS`].
+const DEFAULT_SPEED: usize = 3;
+/// Tempo máximo gasto aplicando transições a cada quadro ao executar a máquina até a
+/// parada ou até um passo. O limite mantém a interface responsiva em máquinas que nunca
+/// param.
+const HALT_BUDGET: Duration = Duration::from_millis(8);
+
+/// Controla a reprodução automática da tela de processamento. As transições são
+/// aplicadas a cada iteração do laço de eventos, de acordo com o tempo decorrido desde a
+/// última transição.
+#[derive(Clone, Copy, Debug)]
+pub struct Playback {
+    mode: PlaybackMode,
+    /// Índice da velocidade atual em [`SPEEDS`].
+    speed: usize,
+    /// Instante em que a última transição automática deveria ter sido aplicada.
+    last_tick: Instant,
+}
+
+/// Os modos da reprodução automática.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum PlaybackMode {
+    Paused,
+    /// Aplica transições na velocidade atual.
+    Playing,
+    /// Aplica transições o mais rápido possível, até a máquina parar.
+    ToHalt,
+    /// Aplica transições o mais rápido possível, até a máquina alcançar o passo dado ou
+    /// parar.
+    ToStep(usize),
+}
+
+impl Playback {
+    pub(crate) fn new() -> Playback {
+        Playback {
+            mode: PlaybackMode::Paused,
+            speed: DEFAULT_SPEED,
+            last_tick: Instant::now(),
+        }
+    }
+
+    /// Trata as teclas da reprodução automática. Retorna `true` caso a tecla tenha sido
+    /// consumida.
+    pub(crate) fn handle_key(&mut self, key: KeyEvent) -> bool {
+        match key.code {
+            KeyCode::Char(' ') => {
+                self.mode = match self.mode {
+                    PlaybackMode::Paused => PlaybackMode::Playing,
+                    _ => PlaybackMode::Paused,
+                };
+                self.last_tick = Instant::now();
+            }
+            KeyCode::Char('+') | KeyCode::Char('=') => {
+                self.speed = (self.speed + 1).min(SPEEDS.len() - 1);
+            }
+            KeyCode::Char('-') => self.speed = self.speed.saturating_sub(1),
+            KeyCode::Enter => self.mode = PlaybackMode::ToHalt,
+            _ => return false,
+        }
+        true
+    }
+
+    /// Avança a máquina até o passo `step`, a partir do passo atual, nas próximas
+    /// iterações do laço de eventos.
+    pub(crate) fn seek(&mut self, step: usize) {
+        self.mode = PlaybackMode::ToStep(step);
+    }
+
+    /// Retorna o número de transições a serem aplicadas nessa iteração do laço de
+    /// eventos, sendo `steps` o passo atual da máquina.
+    fn due(&mut self, steps: usize) -> usize {
+        match self.mode {
+            PlaybackMode::Paused => 0,
+            PlaybackMode::ToHalt => usize::MAX,
+            PlaybackMode::ToStep(step) => step.saturating_sub(steps),
+            PlaybackMode::Playing => {
+                let speed = self.speed() as f64;
+                let due = (self.last_tick.elapsed().as_secs_f64() * speed) as usize;
+                self.last_tick += Duration::from_secs_f64(due as f64 / speed);
+                due
+            }
+        }
+    }
+
+    /// Aplica as transições devidas por meio de `transition`, que retorna `true` quando
+    /// a máquina para. Nesse caso, a reprodução é pausada. `steps` é o passo atual da
+    /// máquina.
+    /// Ao executar até a parada ou até um passo, as transições são aplicadas até esgotar
+    /// [`HALT_BUDGET`]; a reprodução é pausada ao alcançar o passo.
+    pub(crate) fn advance(&mut self, steps: usize, mut transition: impl FnMut() -> bool) {
+        let start = Instant::now();
+        let budgeted = matches!(self.mode, PlaybackMode::ToHalt | PlaybackMode::ToStep(_));
+        for _ in 0..self.due(steps) {
+            if budgeted && start.elapsed() >= HALT_BUDGET {
+                return;
+            }
+            if transition() {
+                self.pause();
+                return;
+            }
+        }
+        if let PlaybackMode::ToStep(_) = self.mode {
+            self.pause();
+        }
+    }
+
+    pub(crate) fn pause(&mut self) {
+        self.mode = PlaybackMode::Paused;
+    }
+
+    pub fn mode(&self) -> PlaybackMode {
+        self.mode
+    }
+
+    /// Retorna a velocidade atual, em transições por segundo.
+    pub fn speed(&self) -> u32 {
+        SPEEDS[self.speed]
+    }
+}
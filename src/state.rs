@@ -1,11 +1,19 @@
-use std::{collections::HashSet, time::Duration};
+use std::time::Duration;
 
 use crossterm::event::{self, Event, KeyCode};
+use indexmap::IndexSet;
 use tui::{backend::Backend, Terminal};
 
 use crate::{
-    draw::{self, original_tape_spans},
-    machine::Machine,
+    draw::{self, original_tape_spans, MachineView},
+    machine::{
+        breakpoint::Breakpoint,
+        multi::MultiMachine,
+        sep::Septuple,
+        symbol::{split_input, Symbol},
+        Machine, OutputMode,
+    },
+    playback::{Playback, PlaybackMode},
     Either::{self, L, R},
     Result,
 };
@@ -16,8 +24,8 @@ pub struct Quit;
 /// inserida tenha apenas símbolos dentro do set de símbolos.
 pub fn read_valid_tape<B: Backend>(
     term: &mut Terminal<B>,
-    input_symbols: &HashSet<char>,
-) -> Result<Either<Vec<char>, Quit>> {
+    input_symbols: &IndexSet<Symbol>,
+) -> Result<Either<Vec<Symbol>, Quit>> {
     loop {
         let tape = match read_any_tape(term, input_symbols)? {
             L(vec) => vec,
@@ -25,7 +33,7 @@ pub fn read_valid_tape<B: Backend>(
                 return Ok(R(quit));
             }
         };
-        if tape.iter().any(|char| !input_symbols.contains(char)) {
+        if tape.iter().any(|symbol| !input_symbols.contains(symbol)) {
             bad_tape(term, input_symbols)?;
         } else {
             return Ok(L(tape));
@@ -33,11 +41,12 @@ pub fn read_valid_tape<B: Backend>(
     }
 }
 
-/// Entra no estado de leitura de fita. Retorna após receber a tecla `Enter`.
+/// Entra no estado de leitura de fita. Retorna após receber a tecla `Enter`, com os
+/// símbolos separados como em [`split_input`].
 fn read_any_tape<B: Backend>(
     term: &mut Terminal<B>,
-    input_symbols: &HashSet<char>,
-) -> Result<Either<Vec<char>, Quit>> {
+    input_symbols: &IndexSet<Symbol>,
+) -> Result<Either<Vec<Symbol>, Quit>> {
     let mut buffer = String::new();
 
     loop {
@@ -54,7 +63,7 @@ fn read_any_tape<B: Backend>(
                     buffer.pop();
                 }
                 KeyCode::Enter => {
-                    return Ok(L(buffer.chars().collect()));
+                    return Ok(L(split_input(&buffer, input_symbols)));
                 }
                 KeyCode::Char(char) => {
                     buffer.push(char);
@@ -70,7 +79,7 @@ fn read_any_tape<B: Backend>(
 
 /// Entra no estado de erro causado por uma inserção de fita inválida. Deixa o estado
 /// após qualquer tecla ser pressionada.
-fn bad_tape<B: Backend>(term: &mut Terminal<B>, input_symbols: &HashSet<char>) -> Result<()> {
+fn bad_tape<B: Backend>(term: &mut Terminal<B>, input_symbols: &IndexSet<Symbol>) -> Result<()> {
     loop {
         term.draw(|f| draw::bad_tape(f, input_symbols))?;
 
@@ -85,28 +94,258 @@ fn bad_tape<B: Backend>(term: &mut Terminal<B>, input_symbols: &HashSet<char>) -
 }
 
 // Entra no estado de processamento da máquina. Retorna quando o usuário aperta `Esc`.
-pub fn process_machine<B: Backend>(term: &mut Terminal<B>, machine: &mut Machine) -> Result<Quit> {
+// Caso `output` seja `Some`, a máquina computa uma função e o resultado é exibido quando
+// ela parar. A reprodução automática é pausada ao atingir algum dos `breakpoints`.
+pub fn process_machine<B: Backend>(
+    term: &mut Terminal<B>,
+    machine: &mut Machine,
+    output: Option<OutputMode>,
+    breakpoints: &mut Vec<Breakpoint>,
+) -> Result<Quit> {
     let og_tape = original_tape_spans(machine.tape());
+    let mut playback = Playback::new();
+    let mut pan = 0;
+    // maior passo já alcançado, usado como fim da linha do tempo
+    let mut furthest = machine.steps();
+    // passo selecionado no histórico; caso `None`, acompanha o passo atual
+    let mut history_cursor: Option<usize> = None;
 
     loop {
+        // ao ir para um passo, os pontos de parada são ignorados
+        let seeking = matches!(playback.mode(), PlaybackMode::ToStep(_));
+        playback.advance(machine.steps(), || {
+            machine.transition().is_some()
+                || (!seeking && breakpoints.iter().any(|b| b.hit(machine)))
+        });
+        furthest = furthest.max(machine.steps());
+        let hit = breakpoints.iter().find(|b| b.hit(machine));
         let acceptance = machine.acceptance();
-        term.draw(|f| draw::machine(f, machine, acceptance, og_tape.clone()))?;
+        let result = match (acceptance, output) {
+            (Some(acceptance), Some(mode)) if acceptance.halted() => Some(machine.output(mode)),
+            _ => None,
+        };
+        let view = MachineView {
+            acceptance,
+            output: result.as_deref(),
+            playback: &playback,
+            hit,
+            pan,
+            furthest,
+            history_cursor,
+        };
+        term.draw(|f| draw::machine(f, machine, og_tape.clone(), &view))?;
 
         if !event::poll(Duration::from_millis(50))? {
             continue;
         }
 
         if let Event::Key(key) = event::read()? {
+            if playback.handle_key(key) {
+                continue;
+            }
             match key.code {
                 KeyCode::Left => {
+                    playback.pause();
                     let _ = machine.undo_transition();
                 }
                 KeyCode::Right => {
                     let _ = machine.transition();
                 }
+                KeyCode::Char('h') => pan = clamp_pan(pan - 1, machine.tape().len()),
+                KeyCode::Char('l') => pan = clamp_pan(pan + 1, machine.tape().len()),
+                KeyCode::Char('c') => pan = 0,
+                KeyCode::Home => {
+                    playback.pause();
+                    machine.seek(0);
+                }
+                KeyCode::End => seek(machine, &mut playback, furthest),
+                KeyCode::PageUp | KeyCode::PageDown => {
+                    let jump = (furthest / 20).max(1);
+                    let step = match key.code {
+                        KeyCode::PageUp => machine.steps().saturating_sub(jump),
+                        _ => machine.steps() + jump,
+                    };
+                    seek(machine, &mut playback, step);
+                }
+                KeyCode::Up => {
+                    let cursor = history_cursor.unwrap_or(machine.steps());
+                    history_cursor = Some(cursor.saturating_sub(1));
+                }
+                KeyCode::Down => {
+                    history_cursor = history_cursor
+                        .map(|cursor| cursor + 1)
+                        .filter(|&cursor| cursor < machine.steps());
+                }
+                KeyCode::Char('r') => {
+                    if let Some(cursor) = history_cursor.take() {
+                        playback.pause();
+                        machine.seek(cursor);
+                    }
+                }
+                KeyCode::Char('g') => {
+                    playback.pause();
+                    if let Some(step) = read_step(term)? {
+                        seek(machine, &mut playback, step);
+                    }
+                }
+                KeyCode::Char('b') => {
+                    playback.pause();
+                    edit_breakpoints(term, breakpoints, machine.septuple())?;
+                }
                 KeyCode::Esc => return Ok(Quit),
                 _ => (),
             }
         }
     }
 }
+
+/// Leva a máquina ao passo `step`. Passos anteriores ao atual são alcançados de imediato,
+/// desfazendo transições; passos posteriores, pela reprodução automática, para que
+/// máquinas que nunca param não bloqueiem a interface.
+fn seek(machine: &mut Machine, playback: &mut Playback, step: usize) {
+    if step <= machine.steps() {
+        playback.pause();
+        machine.seek(step);
+    } else {
+        playback.seek(step);
+    }
+}
+
+/// Entra no estado de leitura do passo para o qual a máquina deve ir. Retorna [`None`]
+/// caso o usuário aperte `Esc`.
+fn read_step<B: Backend>(term: &mut Terminal<B>) -> Result<Option<usize>> {
+    let mut buffer = String::new();
+    let mut invalid = false;
+
+    loop {
+        term.draw(|f| draw::step_input(f, &buffer, invalid))?;
+
+        if !event::poll(Duration::from_millis(50))? {
+            continue;
+        }
+
+        if let Event::Key(key) = event::read()? {
+            match key.code {
+                KeyCode::Backspace => {
+                    buffer.pop();
+                }
+                KeyCode::Enter => match buffer.trim().parse() {
+                    Ok(step) => return Ok(Some(step)),
+                    Err(_) => invalid = true,
+                },
+                KeyCode::Char(char) => {
+                    buffer.push(char);
+                }
+                KeyCode::Esc => return Ok(None),
+                _ => (),
+            }
+        }
+    }
+}
+
+/// Entra no estado de edição dos pontos de parada. Retorna quando o usuário aperta
+/// `Esc`. Pontos de parada com estados ou símbolos fora de `septuple` são recusados.
+fn edit_breakpoints<B: Backend>(
+    term: &mut Terminal<B>,
+    breakpoints: &mut Vec<Breakpoint>,
+    septuple: &Septuple,
+) -> Result<()> {
+    let mut buffer = String::new();
+    let mut selected = 0;
+    let mut error = None;
+
+    loop {
+        term.draw(|f| draw::breakpoints(f, &buffer, breakpoints, selected, error.as_ref()))?;
+
+        if !event::poll(Duration::from_millis(50))? {
+            continue;
+        }
+
+        if let Event::Key(key) = event::read()? {
+            match key.code {
+                KeyCode::Backspace => {
+                    buffer.pop();
+                }
+                KeyCode::Enter => match buffer
+                    .parse::<Breakpoint>()
+                    .and_then(|breakpoint| breakpoint.valid(septuple).map(|()| breakpoint))
+                {
+                    Ok(breakpoint) => {
+                        if !breakpoints.contains(&breakpoint) {
+                            breakpoints.push(breakpoint);
+                        }
+                        buffer.clear();
+                        error = None;
+                    }
+                    Err(err) => error = Some(err),
+                },
+                KeyCode::Char(char) => {
+                    buffer.push(char);
+                }
+                KeyCode::Up => selected = selected.saturating_sub(1),
+                KeyCode::Down if selected + 1 < breakpoints.len() => selected += 1,
+                KeyCode::Delete if selected < breakpoints.len() => {
+                    breakpoints.remove(selected);
+                    selected = selected.min(breakpoints.len().saturating_sub(1));
+                }
+                KeyCode::Esc => return Ok(()),
+                _ => (),
+            }
+        }
+    }
+}
+
+// Entra no estado de processamento de uma máquina multifita. Retorna quando o usuário
+// aperta `Esc`.
+pub fn process_multi_machine<B: Backend>(
+    term: &mut Terminal<B>,
+    machine: &mut MultiMachine,
+) -> Result<Quit> {
+    let mut playback = Playback::new();
+    let mut pan = 0;
+
+    loop {
+        playback.advance(machine.steps(), || machine.transition().is_some());
+        let acceptance = machine.acceptance();
+        term.draw(|f| draw::multi_machine(f, machine, acceptance, &playback, pan))?;
+
+        if !event::poll(Duration::from_millis(50))? {
+            continue;
+        }
+
+        if let Event::Key(key) = event::read()? {
+            if playback.handle_key(key) {
+                continue;
+            }
+            match key.code {
+                KeyCode::Left => {
+                    playback.pause();
+                    let _ = machine.undo_transition();
+                }
+                KeyCode::Right => {
+                    let _ = machine.transition();
+                }
+                KeyCode::Char('h') => pan = clamp_pan(pan - 1, longest_tape(machine)),
+                KeyCode::Char('l') => pan = clamp_pan(pan + 1, longest_tape(machine)),
+                KeyCode::Char('c') => pan = 0,
+                KeyCode::Esc => return Ok(Quit),
+                _ => (),
+            }
+        }
+    }
+}
+
+/// Limita o deslocamento da janela da fita ao tamanho da fita, já que deslocamentos
+/// maiores não alteram a janela exibida.
+fn clamp_pan(pan: isize, tape_len: usize) -> isize {
+    pan.clamp(-(tape_len as isize), tape_len as isize)
+}
+
+fn longest_tape(machine: &MultiMachine) -> usize {
+    machine
+        .tapes()
+        .iter()
+        .map(|tape| tape.len())
+        .max()
+        .unwrap_or(0)
+}